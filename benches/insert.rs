@@ -31,7 +31,7 @@ fn insert_long_str(b: &mut Bencher) {
     let mut m: Map<&str> = Map::with_capacity(CAPACITY);
     b.iter(|| {
         for i in 0..CAPACITY {
-            m.insert(i, &"Hello, world! How are you doing today?");
+            m.insert(i, "Hello, world! How are you doing today?");
         }
     });
 }
@@ -28,7 +28,7 @@ const CAPACITY: usize = 65536;
 
 #[bench]
 fn remove_big_array(b: &mut Bencher) {
-    let mut m: Map<[u8; 1024]> = Map::with_capacity(CAPACITY);
+    let mut m: Map<[u8; 1024]> = Map::with_capacity_none(CAPACITY);
     b.iter(|| {
         for i in 0..CAPACITY {
             m.remove(i);
@@ -38,7 +38,7 @@ fn remove_big_array(b: &mut Bencher) {
 
 #[bench]
 fn remove_bool(b: &mut Bencher) {
-    let mut m: Map<bool> = Map::with_capacity(CAPACITY);
+    let mut m: Map<bool> = Map::with_capacity_none(CAPACITY);
     b.iter(|| {
         for i in 0..CAPACITY {
             m.remove(i);
@@ -48,7 +48,7 @@ fn remove_bool(b: &mut Bencher) {
 
 #[bench]
 fn remove_eight_bytes(b: &mut Bencher) {
-    let mut m: Map<u64> = Map::with_capacity(CAPACITY);
+    let mut m: Map<u64> = Map::with_capacity_none(CAPACITY);
     b.iter(|| {
         for i in 0..CAPACITY {
             m.remove(i);
@@ -58,7 +58,7 @@ fn remove_eight_bytes(b: &mut Bencher) {
 
 #[bench]
 fn remove_four_bytes(b: &mut Bencher) {
-    let mut m: Map<u32> = Map::with_capacity(CAPACITY);
+    let mut m: Map<u32> = Map::with_capacity_none(CAPACITY);
     b.iter(|| {
         for i in 0..CAPACITY {
             m.remove(i);
@@ -0,0 +1,59 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+#![feature(test)]
+
+extern crate test;
+use emap::Map;
+use test::Bencher;
+
+const CAPACITY: usize = 65536;
+
+fn sparse() -> Map<u64> {
+    let mut m = Map::with_capacity_none(CAPACITY);
+    for k in (0..CAPACITY).step_by(3) {
+        m.insert(k, 42);
+    }
+    m
+}
+
+#[bench]
+fn scan_with_iter(b: &mut Bencher) {
+    let m = sparse();
+    b.iter(|| {
+        let mut sum = 0;
+        for (k, v) in m.iter() {
+            sum += k as u64 + v;
+        }
+        sum
+    });
+}
+
+#[bench]
+fn scan_unchecked(b: &mut Bencher) {
+    let m = sparse();
+    b.iter(|| {
+        let mut sum = 0;
+        unsafe {
+            m.for_each_occupied_unchecked(|k, v| sum += k as u64 + v);
+        }
+        sum
+    });
+}
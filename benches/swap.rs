@@ -0,0 +1,49 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+#![feature(test)]
+
+extern crate test;
+use emap::Map;
+use test::Bencher;
+
+const CAPACITY: usize = 65536;
+
+#[bench]
+fn swap_checked(b: &mut Bencher) {
+    let mut m: Map<u64> = Map::with_capacity_some(CAPACITY, 42);
+    b.iter(|| {
+        for i in 1..CAPACITY {
+            let [x, y] = m.get_many_mut([i - 1, i]).unwrap();
+            std::mem::swap(x, y);
+        }
+    });
+}
+
+#[bench]
+fn swap_unchecked(b: &mut Bencher) {
+    let mut m: Map<u64> = Map::with_capacity_some(CAPACITY, 42);
+    b.iter(|| {
+        for i in 1..CAPACITY {
+            let [x, y] = unsafe { m.get_disjoint_unchecked_mut([i - 1, i]) };
+            std::mem::swap(x, y);
+        }
+    });
+}
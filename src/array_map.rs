@@ -0,0 +1,246 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::{raw, ArrayMap, Iter, IterMut};
+use std::marker::PhantomData;
+
+impl<V, const N: usize> Default for ArrayMap<V, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, V, const N: usize> IntoIterator for &'a ArrayMap<V, N> {
+    type Item = (usize, &'a V);
+    type IntoIter = Iter<'a, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, V, const N: usize> IntoIterator for &'a mut ArrayMap<V, N> {
+    type Item = (usize, &'a mut V);
+    type IntoIter = IterMut<'a, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<V, const N: usize> ArrayMap<V, N> {
+    /// Make an empty map.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            max: 0,
+            slots: std::array::from_fn(|_| None),
+        }
+    }
+
+    /// Return capacity.
+    #[inline]
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Is it empty?
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the total number of items inside.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        unsafe { raw::len(self.slots.as_ptr(), self.max) }
+    }
+
+    /// Does the map contain this key?
+    ///
+    /// # Panics
+    ///
+    /// If the key is outside of the boundary of this map.
+    #[inline]
+    #[must_use]
+    pub const fn contains_key(&self, k: usize) -> bool {
+        self.slots[k].is_some()
+    }
+
+    /// Get a reference to a single value.
+    ///
+    /// # Panics
+    ///
+    /// If the key is outside of the boundary of this map.
+    #[inline]
+    #[must_use]
+    pub const fn get(&self, k: usize) -> Option<&V> {
+        self.slots[k].as_ref()
+    }
+
+    /// Get a mutable reference to a single value.
+    ///
+    /// # Panics
+    ///
+    /// If the key is outside of the boundary of this map.
+    #[inline]
+    pub const fn get_mut(&mut self, k: usize) -> Option<&mut V> {
+        self.slots[k].as_mut()
+    }
+
+    /// Insert a single pair into the map.
+    ///
+    /// Just like [`crate::Map::insert`], it doesn't drop the value
+    /// that may be there already.
+    ///
+    /// # Panics
+    ///
+    /// If the key is outside of the boundary of this map.
+    #[inline]
+    pub fn insert(&mut self, k: usize, v: V) {
+        self.assert_boundaries(k);
+        unsafe {
+            raw::insert(self.slots.as_mut_ptr(), k, v);
+        }
+        if self.max <= k {
+            self.max = k + 1;
+        }
+    }
+
+    /// Remove by key.
+    ///
+    /// Just like [`crate::Map::remove`], it doesn't drop the value.
+    ///
+    /// # Panics
+    ///
+    /// If the key is outside of the boundary of this map.
+    #[inline]
+    pub fn remove(&mut self, k: usize) {
+        self.assert_boundaries(k);
+        unsafe {
+            raw::remove(self.slots.as_mut_ptr(), k);
+        }
+    }
+
+    /// Push to the smallest available key and return the key.
+    ///
+    /// # Panics
+    ///
+    /// If no more keys left.
+    #[inline]
+    pub fn push(&mut self, v: V) -> usize {
+        let k = unsafe { raw::next_free(self.slots.as_ptr(), 0, self.max, N) }
+            .expect("No more keys available left");
+        self.insert(k, v);
+        k
+    }
+
+    /// Remove all items from it, dropping their values.
+    #[inline]
+    pub fn clear(&mut self) {
+        unsafe {
+            raw::clear(self.slots.as_mut_ptr(), self.max);
+        }
+        self.max = 0;
+    }
+
+    /// Make an iterator over all items, in ascending order of keys.
+    #[inline]
+    #[must_use]
+    pub const fn iter(&self) -> Iter<'_, V> {
+        Iter {
+            max: self.max,
+            pos: 0,
+            head: self.slots.as_ptr().cast_mut(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Make a mutable iterator over all items, in ascending order of keys.
+    #[inline]
+    pub const fn iter_mut(&mut self) -> IterMut<'_, V> {
+        IterMut {
+            max: self.max,
+            pos: 0,
+            head: self.slots.as_mut_ptr(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Check the boundary, in "debug" and "release" modes alike, since
+    /// the slots are written through a raw pointer.
+    #[inline]
+    fn assert_boundaries(&self, k: usize) {
+        assert!(
+            k < N,
+            "The key {k} is over the boundary {}",
+            self.capacity()
+        );
+    }
+}
+
+#[test]
+fn inserts_removes_and_pushes() {
+    let mut m: ArrayMap<&str, 4> = ArrayMap::new();
+    m.insert(1, "one");
+    m.insert(3, "three");
+    assert_eq!(2, m.len());
+    assert_eq!(Some(&"three"), m.get(3));
+    assert_eq!(0, m.push("zero"));
+    m.remove(1);
+    assert!(!m.contains_key(1));
+    assert_eq!(1, m.push("uno"));
+    assert_eq!(2, m.push("two"));
+    m.clear();
+    assert!(m.is_empty());
+}
+
+#[test]
+fn iterates_in_order() {
+    let mut m: ArrayMap<u32, 8> = ArrayMap::default();
+    m.insert(5, 50);
+    m.insert(2, 20);
+    for (_, v) in &mut m {
+        *v += 1;
+    }
+    assert_eq!(vec![(2, &21), (5, &51)], m.iter().collect::<Vec<_>>());
+    assert_eq!(Some((5, &51)), m.iter().next_back());
+}
+
+#[test]
+#[should_panic(expected = "No more keys available left")]
+fn panics_when_array_map_is_full() {
+    let mut m: ArrayMap<u8, 1> = ArrayMap::new();
+    m.push(1);
+    m.push(2);
+}
+
+#[test]
+#[should_panic(expected = "The key 2 is over the boundary 2")]
+fn panics_on_key_over_boundary() {
+    let mut m: ArrayMap<u8, 2> = ArrayMap::new();
+    m.insert(2, 1);
+}
@@ -18,38 +18,46 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use crate::Map;
-use std::alloc::{alloc, dealloc, Layout};
+use crate::{AllocError, Map, MapFullError};
+use std::alloc::{alloc, dealloc, handle_alloc_error, realloc, Layout};
 use std::mem;
+use std::mem::ManuallyDrop;
+use std::ptr;
+use std::ptr::NonNull;
 
 impl<V> Drop for Map<V> {
     fn drop(&mut self) {
-        unsafe {
-            dealloc(self.head.cast(), self.layout);
+        if self.layout.size() > 0 {
+            unsafe {
+                dealloc(self.head.cast(), self.layout);
+            }
         }
     }
 }
 
 impl<V: Clone> Map<V> {
+    /// The biggest capacity a map of this type may have, limited by
+    /// the size of memory layout that Rust allows to allocate.
+    ///
+    /// The actual allocation of that many slots will most probably
+    /// fail anyway, but a bigger capacity is rejected without even trying.
+    pub const MAX_CAPACITY: usize = if mem::size_of::<Option<V>>() == 0 {
+        usize::MAX
+    } else {
+        (isize::MAX.unsigned_abs() - (mem::align_of::<Option<V>>() - 1))
+            / mem::size_of::<Option<V>>()
+    };
+
     /// Make it.
     ///
     /// # Panics
     ///
-    /// May panic if out of memory.
+    /// If the capacity is too big for a memory layout. Aborts the
+    /// process if out of memory.
     #[inline]
     #[must_use]
     pub fn with_capacity(cap: usize) -> Self {
-        unsafe {
-            let layout = Layout::array::<Option<V>>(cap).unwrap();
-            let ptr = alloc(layout);
-            Self {
-                max: 0,
-                layout,
-                head: ptr.cast(),
-                #[cfg(debug_assertions)]
-                initialized: false,
-            }
-        }
+        Self::try_with_capacity(cap).unwrap_or_else(|e| Self::alloc_failed(cap, e))
     }
 
     /// Make it and prepare all keys.
@@ -59,19 +67,32 @@ impl<V: Clone> Map<V> {
     ///
     /// # Panics
     ///
-    /// May panic if out of memory.
+    /// If the capacity is too big for a memory layout. Aborts the
+    /// process if out of memory.
     #[inline]
     #[must_use]
     pub fn with_capacity_none(cap: usize) -> Self {
-        let mut m = Self::with_capacity(cap);
-        for k in 0..cap {
-            m.remove(k);
-        }
-        #[cfg(debug_assertions)]
-        {
-            m.initialized = true;
-        }
-        m
+        Self::try_with_capacity_none(cap).unwrap_or_else(|e| Self::alloc_failed(cap, e))
+    }
+
+    /// Make it and prepare all keys, rounding the capacity up to
+    /// a multiple of `reg_size`.
+    ///
+    /// It gives predictable lengths of the slot array, for example
+    /// for vectorized scans over [`Map::as_option_slice`].
+    ///
+    /// # Panics
+    ///
+    /// If `reg_size` is zero, or if the rounded capacity is too big.
+    /// Aborts the process if out of memory.
+    #[inline]
+    #[must_use]
+    pub fn with_capacity_none_aligned(min_cap: usize, reg_size: usize) -> Self {
+        assert_ne!(0, reg_size, "The register size can't be zero");
+        let cap = min_cap
+            .checked_next_multiple_of(reg_size)
+            .unwrap_or_else(|| panic!("The capacity {min_cap} is too big"));
+        Self::with_capacity_none(cap)
     }
 
     /// Make it and prepare all keys with some value set.
@@ -81,11 +102,91 @@ impl<V: Clone> Map<V> {
     ///
     /// # Panics
     ///
-    /// May panic if out of memory.
+    /// If the capacity is too big for a memory layout. Aborts the
+    /// process if out of memory.
     #[inline]
     #[must_use]
     pub fn with_capacity_some(cap: usize, v: V) -> Self {
-        let mut m = Self::with_capacity(cap);
+        Self::try_with_capacity_some(cap, v).unwrap_or_else(|e| Self::alloc_failed(cap, e))
+    }
+
+    /// Make it, or return an error if the memory can't be allocated.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity is too big for a memory layout or the allocator
+    /// fails to provide the memory.
+    #[inline]
+    pub fn try_with_capacity(cap: usize) -> Result<Self, AllocError> {
+        if cap > Self::MAX_CAPACITY {
+            return Err(AllocError::CapacityOverflow);
+        }
+        let layout = Layout::array::<Option<V>>(cap).map_err(|_| AllocError::CapacityOverflow)?;
+        let head: *mut Option<V> = if layout.size() == 0 {
+            NonNull::dangling().as_ptr()
+        } else {
+            unsafe { alloc(layout) }.cast()
+        };
+        if head.is_null() {
+            return Err(AllocError::OutOfMemory);
+        }
+        let m = Self {
+            min: 0,
+            max: 0,
+            layout,
+            head,
+            #[cfg(debug_assertions)]
+            initialized: false,
+            #[cfg(feature = "ordered")]
+            seqs: vec![],
+            #[cfg(feature = "ordered")]
+            seq: 0,
+        };
+        #[cfg(feature = "ordered")]
+        let m = {
+            let mut m = m;
+            m.seqs
+                .try_reserve_exact(cap)
+                .map_err(|_| AllocError::OutOfMemory)?;
+            m.seqs.resize(cap, 0);
+            m
+        };
+        Ok(m)
+    }
+
+    /// Make it and prepare all keys, or return an error if the memory
+    /// can't be allocated.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity is too big for a memory layout or the allocator
+    /// fails to provide the memory.
+    #[inline]
+    pub fn try_with_capacity_none(cap: usize) -> Result<Self, AllocError> {
+        #[allow(unused_mut)]
+        let mut m = Self::try_with_capacity(cap)?;
+        for k in 0..cap {
+            unsafe {
+                ptr::write(m.head.add(k), None);
+            }
+        }
+        #[cfg(debug_assertions)]
+        {
+            m.initialized = true;
+        }
+        Ok(m)
+    }
+
+    /// Make it and prepare all keys with some value set, or return an
+    /// error if the memory can't be allocated.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity is too big for a memory layout or the allocator
+    /// fails to provide the memory.
+    #[inline]
+    pub fn try_with_capacity_some(cap: usize, v: V) -> Result<Self, AllocError> {
+        let mut m = Self::try_with_capacity(cap)?;
         for k in 0..cap {
             m.insert(k, v.clone());
         }
@@ -93,9 +194,264 @@ impl<V: Clone> Map<V> {
         {
             m.initialized = true;
         }
+        Ok(m)
+    }
+
+    /// Report a failed allocation the same way `Vec` does.
+    fn alloc_failed(cap: usize, e: AllocError) -> ! {
+        match e {
+            AllocError::CapacityOverflow => panic!("The capacity {cap} is too big"),
+            AllocError::OutOfMemory => handle_alloc_error(Layout::array::<Option<V>>(cap).unwrap()),
+        }
+    }
+
+    /// Calculate the minimum capacity that is enough to hold all the keys.
+    ///
+    /// For example, a map with keys `3` and `7` needs a capacity of `8`:
+    ///
+    /// ```
+    /// use emap::Map;
+    /// assert_eq!(Ok(8), Map::<u8>::capacity_for_keys([3, 7]));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the key `usize::MAX` is among the keys, since no capacity
+    /// is big enough for it.
+    #[inline]
+    pub fn capacity_for_keys<I: IntoIterator<Item = usize>>(
+        keys: I,
+    ) -> Result<usize, MapFullError> {
+        let mut cap = 0;
+        for k in keys {
+            let next = k.checked_add(1).ok_or(MapFullError)?;
+            if next > cap {
+                cap = next;
+            }
+        }
+        Ok(cap)
+    }
+
+    /// Make a map with the keys provided, computing each value
+    /// from its key.
+    ///
+    /// The capacity is just enough for the largest key, as
+    /// [`Map::capacity_for_keys`] calculates it.
+    ///
+    /// # Errors
+    ///
+    /// If the key `usize::MAX` is among the keys.
+    ///
+    /// # Panics
+    ///
+    /// If there is not enough memory, just like [`Map::with_capacity_none`].
+    #[inline]
+    pub fn from_keys_with<I: IntoIterator<Item = usize>, F: FnMut(usize) -> V>(
+        keys: I,
+        mut f: F,
+    ) -> Result<Self, MapFullError> {
+        let keys: Vec<usize> = keys.into_iter().collect();
+        let mut m = Self::with_capacity_none(Self::capacity_for_keys(keys.iter().copied())?);
+        for k in keys {
+            m.insert(k, f(k));
+        }
+        Ok(m)
+    }
+
+    /// Decompose the map into the pointer to its slots, the layout of
+    /// their memory, and the top of the used range of keys.
+    ///
+    /// The map is not dropped, so the memory and the values stay alive
+    /// until the parts are put back together by [`Map::from_raw_parts`],
+    /// or the caller frees them in some other way.
+    ///
+    /// With the `ordered` feature, the insertion sequence numbers are
+    /// not a part of the raw parts, so they are lost.
+    #[inline]
+    #[must_use]
+    pub fn into_raw_parts(self) -> (*mut Option<V>, Layout, usize) {
+        let this = ManuallyDrop::new(self);
+        #[cfg(feature = "ordered")]
+        drop(unsafe { std::ptr::read(std::ptr::addr_of!(this.seqs)) });
+        (this.head, this.layout, this.max)
+    }
+
+    /// Make a map from the parts made by [`Map::into_raw_parts`],
+    /// without allocating anything.
+    ///
+    /// # Safety
+    ///
+    /// The `layout` must be the layout of an array of `Option<V>`. If its
+    /// size is not zero, the `head` must point to the memory allocated by
+    /// the global allocator with exactly this layout, otherwise it must be
+    /// a dangling, well-aligned pointer. All slots must be initialized,
+    /// the ones at `max` and above must be `None`, and `max` must not be
+    /// bigger than the number of slots. The map takes ownership of the
+    /// memory, so nothing else may use or free it afterwards.
+    ///
+    /// The bottom of the used range of keys is found again, by looking
+    /// at the slots from zero up. With the `ordered` feature, all keys
+    /// get the same insertion sequence number, since it is not a part of
+    /// the raw parts, so [`Map::oldest_key`] is the smallest key.
+    #[inline]
+    #[must_use]
+    pub unsafe fn from_raw_parts(head: *mut Option<V>, layout: Layout, max: usize) -> Self {
+        let mut m = Self {
+            min: 0,
+            max,
+            head,
+            layout,
+            #[cfg(debug_assertions)]
+            initialized: true,
+            #[cfg(feature = "ordered")]
+            seqs: vec![0; layout.size() / mem::size_of::<Option<V>>()],
+            #[cfg(feature = "ordered")]
+            seq: 0,
+        };
+        m.tighten();
         m
     }
 
+    /// Leak the map, so that it is never dropped, and return
+    /// a reference to it that lives forever.
+    ///
+    /// Just like [`Box::leak`], it's useful for tables that are set up
+    /// once and used until the end of the program, for example in
+    /// a `OnceLock`. Neither the map nor its values are ever freed, so
+    /// leak detectors, like Miri or Valgrind, will report them.
+    #[inline]
+    #[must_use]
+    pub fn leak(self) -> &'static mut Self
+    where
+        V: 'static,
+    {
+        Box::leak(Box::new(self))
+    }
+
+    /// Grow the capacity, if necessary, to make the key fit into the
+    /// boundary, so that the next [`Map::insert`] of it can't panic.
+    ///
+    /// The slots are reallocated to exactly `k + 1`, keeping all items
+    /// in place. If the key is already inside the boundary, nothing happens.
+    ///
+    /// # Panics
+    ///
+    /// If the new capacity is too big for a memory layout. Aborts the
+    /// process if out of memory.
+    ///
+    /// It may also panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    pub fn reserve_for_key(&mut self, k: usize) {
+        #[cfg(debug_assertions)]
+        assert!(
+            self.initialized,
+            "Can't reserve_for_key() non-initialized Map"
+        );
+        if k < self.capacity() {
+            return;
+        }
+        self.grow_to(k.saturating_add(1));
+    }
+
+    /// Insert a single pair into the map, growing its capacity first,
+    /// if the key is outside of the boundary, and return the value
+    /// that was there before.
+    ///
+    /// The capacity is at least doubled on every growth, so a sequence of
+    /// insertions of growing keys costs amortized constant time for each
+    /// of them, just like [`Vec::push`]. Every growth reallocates the
+    /// slots and may move them in memory.
+    ///
+    /// # Panics
+    ///
+    /// If the new capacity is too big for a memory layout. Aborts the
+    /// process if out of memory.
+    ///
+    /// It may also panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    pub fn grow_and_insert(&mut self, k: usize, v: V) -> Option<V> {
+        #[cfg(debug_assertions)]
+        assert!(
+            self.initialized,
+            "Can't grow_and_insert() non-initialized Map"
+        );
+        if k >= self.capacity() {
+            let mut cap = self.capacity().saturating_mul(2).max(k.saturating_add(1));
+            if cap > Self::MAX_CAPACITY {
+                cap = k.saturating_add(1);
+            }
+            self.grow_to(cap);
+        }
+        self.checked_insert(k, v).ok().flatten()
+    }
+
+    /// Reallocate the slots to the bigger capacity, filling the new
+    /// ones with `None`.
+    fn grow_to(&mut self, cap: usize) {
+        if cap > Self::MAX_CAPACITY {
+            Self::alloc_failed(cap, AllocError::CapacityOverflow);
+        }
+        let old = self.capacity();
+        let layout = Layout::array::<Option<V>>(cap)
+            .unwrap_or_else(|_| Self::alloc_failed(cap, AllocError::CapacityOverflow));
+        let head: *mut Option<V> = if self.layout.size() == 0 {
+            unsafe { alloc(layout) }.cast()
+        } else {
+            unsafe { realloc(self.head.cast(), self.layout, layout.size()) }.cast()
+        };
+        if head.is_null() {
+            handle_alloc_error(layout);
+        }
+        for k in old..cap {
+            unsafe {
+                ptr::write(head.add(k), None);
+            }
+        }
+        self.head = head;
+        self.layout = layout;
+        #[cfg(feature = "ordered")]
+        self.seqs.resize(cap, 0);
+    }
+
+    /// Shrink the capacity down to the largest occupied key plus one,
+    /// returning the number of slots freed.
+    ///
+    /// An empty map shrinks to zero capacity. The slots are reallocated,
+    /// keeping all items in place.
+    ///
+    /// # Panics
+    ///
+    /// Aborts the process if the allocator fails to shrink the memory.
+    ///
+    /// It may also panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    pub fn trim(&mut self) -> usize {
+        let cap = self.last_key().map_or(0, |k| k + 1);
+        let old = self.capacity();
+        if cap == old {
+            return 0;
+        }
+        let layout = Layout::array::<Option<V>>(cap).unwrap();
+        if cap == 0 {
+            unsafe {
+                dealloc(self.head.cast(), self.layout);
+            }
+            self.head = NonNull::dangling().as_ptr();
+        } else {
+            let head: *mut Option<V> =
+                unsafe { realloc(self.head.cast(), self.layout, layout.size()) }.cast();
+            if head.is_null() {
+                handle_alloc_error(layout);
+            }
+            self.head = head;
+        }
+        self.layout = layout;
+        self.max = cap;
+        #[cfg(feature = "ordered")]
+        self.seqs.truncate(cap);
+        old - cap
+    }
+
     /// Return capacity.
     #[inline]
     #[must_use]
@@ -112,6 +468,29 @@ fn calculates_size_of_memory() {
     assert_eq!(8, m2.layout.size());
 }
 
+#[test]
+fn calculates_capacity_for_no_keys() {
+    assert_eq!(Ok(0), Map::<u8>::capacity_for_keys([]));
+}
+
+#[test]
+fn calculates_capacity_for_single_key() {
+    assert_eq!(Ok(6), Map::<u8>::capacity_for_keys([5]));
+}
+
+#[test]
+fn calculates_capacity_for_sparse_keys() {
+    assert_eq!(Ok(8), Map::<u8>::capacity_for_keys([3, 7, 1]));
+}
+
+#[test]
+fn rejects_capacity_overflow() {
+    assert_eq!(
+        Err(MapFullError),
+        Map::<u8>::capacity_for_keys([1, usize::MAX])
+    );
+}
+
 #[test]
 fn makes_new_map() {
     let m: Map<&str> = Map::with_capacity_none(16);
@@ -124,6 +503,40 @@ fn returns_capacity() {
     assert_eq!(16, m.capacity());
 }
 
+#[test]
+fn tries_reasonable_capacity() {
+    let m: Map<u64> = Map::try_with_capacity_none(16).unwrap();
+    assert_eq!(16, m.capacity());
+    assert!(m.is_empty());
+}
+
+#[test]
+fn tries_some_with_reasonable_capacity() {
+    let m: Map<u64> = Map::try_with_capacity_some(4, 42).unwrap();
+    assert_eq!(4, m.len());
+}
+
+#[test]
+fn fails_on_layout_overflow() {
+    assert_eq!(
+        Some(AllocError::CapacityOverflow),
+        Map::<u64>::try_with_capacity_none(usize::MAX).err()
+    );
+}
+
+#[test]
+#[should_panic(expected = "is too big")]
+fn panics_on_layout_overflow() {
+    let _ = Map::<u64>::with_capacity_none(usize::MAX);
+}
+
+#[test]
+fn makes_empty_map() {
+    let m: Map<u64> = Map::with_capacity_none(0);
+    assert_eq!(0, m.capacity());
+    assert!(m.is_empty());
+}
+
 #[test]
 fn with_init() {
     let m: Map<&str> = Map::with_capacity_none(16);
@@ -137,7 +550,7 @@ fn drops_correctly() {
 }
 
 #[test]
-#[ignore]
+#[ignore = "values are not dropped together with the Map"]
 fn drops_values() {
     use std::rc::Rc;
     let mut m: Map<Rc<()>> = Map::with_capacity(1);
@@ -164,3 +577,181 @@ fn init_with_some() {
     let m: Map<Foo> = Map::with_capacity_some(16, Foo { t: 42 });
     assert_eq!(16, m.capacity());
 }
+
+#[test]
+fn makes_map_from_keys() {
+    let m: Map<usize> = Map::from_keys_with([5, 1, 9], |k| k * 10).unwrap();
+    assert_eq!(10, m.capacity());
+    assert_eq!(vec![1, 5, 9], m.keys().collect::<Vec<_>>());
+    assert_eq!(vec![10, 50, 90], m.values().copied().collect::<Vec<_>>());
+}
+
+#[test]
+fn rejects_max_key_in_from_keys() {
+    assert_eq!(
+        Some(MapFullError),
+        Map::<u8>::from_keys_with([0, usize::MAX], |_| 0).err()
+    );
+}
+
+#[test]
+fn leaks_into_static() {
+    let mut m: Map<&str> = Map::with_capacity_none(4);
+    m.insert(1, "one");
+    let leaked: &'static mut Map<&str> = m.leak();
+    leaked.insert(2, "two");
+    assert_eq!(2, leaked.len());
+    let shared: &'static Map<&str> = leaked;
+    assert_eq!("two", shared[2]);
+}
+
+#[test]
+fn decomposes_and_reassembles() {
+    let mut before: Map<String> = Map::with_capacity_none(8);
+    before.insert(1, "one".to_string());
+    before.insert(5, "five".to_string());
+    before.remove(1);
+    let copy = before.clone();
+    let next = before.next_key();
+    let (head, layout, max) = before.into_raw_parts();
+    assert_eq!(6, max);
+    let after = unsafe { Map::from_raw_parts(head, layout, max) };
+    assert_eq!(copy, after);
+    assert_eq!(next, after.next_key());
+    assert_eq!(8, after.capacity());
+    assert_eq!((5, 6), (after.min, after.max));
+}
+
+#[test]
+fn decomposes_empty_map() {
+    let (head, layout, max) = Map::<u8>::with_capacity_none(0).into_raw_parts();
+    let after = unsafe { Map::from_raw_parts(head, layout, max) };
+    assert!(after.is_empty());
+}
+
+#[test]
+fn rounds_capacity_up() {
+    let m: Map<u8> = Map::with_capacity_none_aligned(10, 8);
+    assert_eq!(16, m.capacity());
+    assert!(m.as_option_slice().iter().all(Option::is_none));
+    assert_eq!(16, Map::<u8>::with_capacity_none_aligned(16, 8).capacity());
+    assert_eq!(0, Map::<u8>::with_capacity_none_aligned(0, 8).capacity());
+}
+
+#[test]
+#[should_panic(expected = "The register size can't be zero")]
+fn panics_on_zero_register_size() {
+    let _ = Map::<u8>::with_capacity_none_aligned(10, 0);
+}
+
+#[test]
+#[should_panic(expected = "is too big")]
+fn panics_on_rounding_overflow() {
+    let _ = Map::<u8>::with_capacity_none_aligned(usize::MAX, 8);
+}
+
+#[test]
+fn calculates_max_capacity() {
+    assert_eq!(isize::MAX.unsigned_abs(), Map::<()>::MAX_CAPACITY);
+    assert!(Layout::array::<Option<u64>>(Map::<u64>::MAX_CAPACITY).is_ok());
+    assert!(Layout::array::<Option<u64>>(Map::<u64>::MAX_CAPACITY + 1).is_err());
+    assert_eq!(
+        Some(AllocError::CapacityOverflow),
+        Map::<u64>::try_with_capacity_none(Map::<u64>::MAX_CAPACITY + 1).err()
+    );
+}
+
+#[test]
+#[should_panic(expected = "is too big")]
+fn panics_over_max_capacity() {
+    let _ = Map::<u32>::with_capacity_none(Map::<u32>::MAX_CAPACITY + 1);
+}
+
+#[test]
+fn reserves_for_key() {
+    let mut m: Map<String> = Map::with_capacity_none(4);
+    m.insert(1, "one".to_string());
+    m.insert(3, "three".to_string());
+    m.reserve_for_key(2);
+    assert_eq!(4, m.capacity());
+    m.reserve_for_key(100);
+    assert_eq!(101, m.capacity());
+    assert!(!m.contains_key(50));
+    m.insert(100, "hundred".to_string());
+    assert_eq!(vec![1, 3, 100], m.keys().collect::<Vec<_>>());
+    assert_eq!("three", m[3]);
+    assert_eq!(2, m.next_key_gte(2));
+}
+
+#[test]
+fn reserves_for_key_in_empty_map() {
+    let mut m: Map<u64> = Map::with_capacity_none(0);
+    m.reserve_for_key(7);
+    assert_eq!(8, m.capacity());
+    m.insert(7, 42);
+    assert_eq!(Some(&42), m.get(7));
+}
+
+#[test]
+#[should_panic(expected = "is too big")]
+fn panics_on_reserving_for_huge_key() {
+    let mut m: Map<u64> = Map::with_capacity_none(1);
+    m.reserve_for_key(usize::MAX);
+}
+
+#[test]
+fn grows_and_inserts() {
+    let mut m: Map<u32> = Map::with_capacity_none(2);
+    assert_eq!(None, m.grow_and_insert(0, 10));
+    assert_eq!(None, m.grow_and_insert(2, 12));
+    assert_eq!(4, m.capacity());
+    assert_eq!(None, m.grow_and_insert(100, 100));
+    assert_eq!(101, m.capacity());
+    assert_eq!(Some(12), m.grow_and_insert(2, 22));
+    assert_eq!(vec![0, 2, 100], m.keys().collect::<Vec<_>>());
+    assert_eq!(vec![10, 22, 100], m.values().copied().collect::<Vec<_>>());
+    assert_eq!(1, m.next_key());
+    assert_eq!(50, m.next_key_gte(50));
+}
+
+#[test]
+fn grows_by_doubling() {
+    let mut m: Map<usize> = Map::with_capacity_none(1);
+    for k in 0..100 {
+        m.grow_and_insert(k, k);
+    }
+    assert_eq!(128, m.capacity());
+    assert_eq!(100, m.len());
+    assert!(m.iter().all(|(k, v)| k == *v));
+}
+
+#[test]
+fn trims_the_tail() {
+    let mut m: Map<String> = Map::with_capacity_none(16);
+    for k in [1, 4, 9, 13] {
+        m.insert(k, k.to_string());
+    }
+    m.remove(9);
+    m.remove(13);
+    assert_eq!(11, m.trim());
+    assert_eq!(5, m.capacity());
+    assert_eq!(vec![1, 4], m.keys().collect::<Vec<_>>());
+    assert_eq!("4", m[4]);
+    assert_eq!(0, m.trim());
+    assert_eq!(0, m.next_key());
+    m.insert(2, "two".to_string());
+    assert_eq!(3, m.len());
+}
+
+#[test]
+fn trims_empty_map_to_zero() {
+    let mut m: Map<u64> = Map::with_capacity_none(8);
+    m.insert(3, 42);
+    m.remove(3);
+    assert_eq!(8, m.trim());
+    assert_eq!(0, m.capacity());
+    assert!(m.is_empty());
+    m.reserve_for_key(2);
+    m.insert(2, 42);
+    assert_eq!(Some(&42), m.get(2));
+}
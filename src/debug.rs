@@ -40,12 +40,53 @@ impl<V: Clone + Display> Debug for Map<V> {
     }
 }
 
+impl<V: Clone + Display> Map<V> {
+    /// Make a printable view of the map, which lists the items without
+    /// sorting them by keys, as [`Display`] of the map itself does.
+    ///
+    /// With the `ordered` feature, the items are listed in the order of
+    /// their insertion. Without it, the insertion order is not tracked,
+    /// so the items are listed in the order of slots, which is the
+    /// ascending order of keys, exactly as [`Display`] of the map prints
+    /// them.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    #[must_use]
+    pub fn display_unsorted(&self) -> impl Display + '_ {
+        Unsorted(self)
+    }
+}
+
+/// The items of a [`Map`], printed without sorting by keys.
+struct Unsorted<'a, V>(&'a Map<V>);
+
+impl<V: Clone + Display> Display for Unsorted<'_, V> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        #[cfg(feature = "ordered")]
+        let keys = {
+            let mut keys: Vec<usize> = self.0.keys().collect();
+            keys.sort_unstable_by_key(|k| self.0.seqs[*k]);
+            keys
+        };
+        #[cfg(not(feature = "ordered"))]
+        let keys = self.0.keys();
+        let parts: Vec<String> = keys
+            .into_iter()
+            .map(|k| format!("{k}: {}", self.0[k]))
+            .collect();
+        f.write_str(format!("{{{}}}", parts.join(", ").as_str()).as_str())
+    }
+}
+
 #[test]
 fn debugs_map() {
     let mut m: Map<&str> = Map::with_capacity_none(16);
     m.insert(0, "one");
     m.insert(1, "two");
-    assert_eq!("{0: one, 1: two}", format!("{:?}", m));
+    assert_eq!("{0: one, 1: two}", format!("{m:?}"));
 }
 
 #[test]
@@ -53,5 +94,16 @@ fn displays_map() {
     let mut m: Map<&str> = Map::with_capacity_none(16);
     m.insert(0, "one");
     m.insert(1, "two");
-    assert_eq!("{0: one, 1: two}", format!("{}", m));
+    assert_eq!("{0: one, 1: two}", format!("{m}"));
+}
+
+#[test]
+#[cfg(not(feature = "ordered"))]
+fn displays_in_slot_order() {
+    let mut m: Map<&str> = Map::with_capacity_none(8);
+    m.insert(5, "a");
+    m.insert(2, "b");
+    m.insert(7, "c");
+    assert_eq!("{2: b, 5: a, 7: c}", m.display_unsorted().to_string());
+    assert_eq!(format!("{m}"), m.display_unsorted().to_string());
 }
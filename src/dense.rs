@@ -0,0 +1,168 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::{Dense, Map};
+use serde::de::{Error, SeqAccess, Visitor};
+use serde::ser::{SerializeSeq, SerializeTuple};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::Formatter;
+use std::marker::PhantomData;
+
+/// The values of a fully occupied [`Map`], in the order of keys.
+struct Values<'a, V>(&'a Map<V>);
+
+impl<V: Clone + Serialize> Serialize for Values<'_, V> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+        for v in self.0.values() {
+            seq.serialize_element(v)?;
+        }
+        seq.end()
+    }
+}
+
+impl<V: Clone + Serialize> Serialize for Dense<V> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let dense = self.0.len() == self.0.capacity();
+        let mut tuple = serializer.serialize_tuple(2)?;
+        tuple.serialize_element(&dense)?;
+        if dense {
+            tuple.serialize_element(&Values(&self.0))?;
+        } else {
+            tuple.serialize_element(&self.0)?;
+        }
+        tuple.end()
+    }
+}
+
+/// A fully occupied [`Map`], made of a sequence of values.
+struct Full<V>(Map<V>);
+
+struct FullVi<V>(PhantomData<V>);
+
+impl<'de, V: Clone + Deserialize<'de>> Visitor<'de> for FullVi<V> {
+    type Value = Full<V>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a sequence of values")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut values = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(v) = seq.next_element()? {
+            values.push(v);
+        }
+        let mut m: Map<V> = Map::try_with_capacity_none(values.len()).map_err(A::Error::custom)?;
+        for (k, v) in values.into_iter().enumerate() {
+            m.insert(k, v);
+        }
+        Ok(Full(m))
+    }
+}
+
+impl<'de, V: Clone + Deserialize<'de>> Deserialize<'de> for Full<V> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(FullVi(PhantomData))
+    }
+}
+
+struct Vi<V>(PhantomData<V>);
+
+impl<'de, V: Clone + Deserialize<'de>> Visitor<'de> for Vi<V> {
+    type Value = Dense<V>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a dense or sparse Map")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let dense: bool = seq
+            .next_element()?
+            .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+        let m = if dense {
+            seq.next_element::<Full<V>>()?.map(|f| f.0)
+        } else {
+            seq.next_element::<Map<V>>()?
+        };
+        m.map(Dense)
+            .ok_or_else(|| A::Error::invalid_length(1, &self))
+    }
+}
+
+impl<'de, V: Clone + Deserialize<'de>> Deserialize<'de> for Dense<V> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(2, Vi(PhantomData))
+    }
+}
+
+#[cfg(test)]
+use bincode::{deserialize, serialize};
+
+#[test]
+fn dense_round_trip() {
+    let before: Map<u32> = Map::with_capacity_some(4, 7);
+    let bytes = serialize(&Dense(before.clone())).unwrap();
+    assert!(bytes.len() < serialize(&before).unwrap().len());
+    let after: Dense<u32> = deserialize(&bytes).unwrap();
+    assert_eq!(4, after.0.capacity());
+    assert_eq!(4, after.0.len());
+    assert_eq!(
+        vec![7, 7, 7, 7],
+        after.0.values().copied().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn sparse_round_trip() {
+    let mut before: Map<u32> = Map::with_capacity_none(8);
+    before.insert(1, 10);
+    before.insert(5, 50);
+    let bytes = serialize(&Dense(before)).unwrap();
+    let after: Dense<u32> = deserialize(&bytes).unwrap();
+    assert_eq!(2, after.0.len());
+    assert_eq!(50, after.0[5]);
+    assert!(!after.0.contains_key(0));
+}
+
+#[test]
+fn empty_round_trip() {
+    let before: Map<u32> = Map::with_capacity_none(0);
+    let bytes = serialize(&Dense(before)).unwrap();
+    let after: Dense<u32> = deserialize(&bytes).unwrap();
+    assert!(after.0.is_empty());
+}
@@ -0,0 +1,251 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::{Entry, Map, OccupiedEntry, VacantEntry};
+use std::ptr;
+
+impl<V: Clone> Map<V> {
+    /// Get the entry of the key, for in-place manipulation.
+    ///
+    /// For example, here is how occurrences can be counted:
+    ///
+    /// ```
+    /// use emap::Map;
+    /// let mut m: Map<u64> = Map::with_capacity_none(16);
+    /// for k in [3, 5, 3] {
+    ///     m.entry(k).and_modify(|c| *c += 1).or_insert(1);
+    /// }
+    /// assert_eq!(2, m[3]);
+    /// assert_eq!(1, m[5]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the key is outside of the boundary of this map, both in "debug"
+    /// and "release" modes, since a vacant entry must be able to hold
+    /// a value. Use [`Map::checked_insert`] for untrusted keys.
+    #[inline]
+    pub fn entry(&mut self, k: usize) -> Entry<'_, V> {
+        assert!(
+            k < self.capacity(),
+            "The key {k} is over the boundary {}",
+            self.capacity()
+        );
+        if self.contains_key(k) {
+            Entry::Occupied(OccupiedEntry { key: k, map: self })
+        } else {
+            Entry::Vacant(VacantEntry { key: k, map: self })
+        }
+    }
+}
+
+impl<'a, V: Clone> Entry<'a, V> {
+    /// Get the key of the entry, either occupied or vacant.
+    #[inline]
+    #[must_use]
+    pub const fn key(&self) -> usize {
+        match self {
+            Entry::Occupied(e) => e.key(),
+            Entry::Vacant(e) => e.key(),
+        }
+    }
+
+    /// Modify the value, if the key is occupied.
+    #[inline]
+    #[must_use]
+    pub fn and_modify<F: FnOnce(&mut V)>(self, f: F) -> Self {
+        match self {
+            Entry::Occupied(mut e) => {
+                f(e.get_mut());
+                Entry::Occupied(e)
+            }
+            Entry::Vacant(e) => Entry::Vacant(e),
+        }
+    }
+
+    /// Get the value, inserting the one provided if the key is vacant.
+    #[inline]
+    pub fn or_insert(self, v: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(e) => e.into_mut(),
+            Entry::Vacant(e) => e.insert(v),
+        }
+    }
+
+    /// Get the value, inserting the one made by the function
+    /// if the key is vacant.
+    #[inline]
+    pub fn or_insert_with<F: FnOnce() -> V>(self, f: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(e) => e.into_mut(),
+            Entry::Vacant(e) => e.insert(f()),
+        }
+    }
+
+    /// Get the value, inserting the one made by the function from
+    /// the key if the key is vacant.
+    #[inline]
+    pub fn or_insert_with_key<F: FnOnce(usize) -> V>(self, f: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(e) => e.into_mut(),
+            Entry::Vacant(e) => {
+                let v = f(e.key());
+                e.insert(v)
+            }
+        }
+    }
+}
+
+impl<'a, V: Clone + Default> Entry<'a, V> {
+    /// Get the value, inserting the default one if the key is vacant.
+    #[inline]
+    pub fn or_default(self) -> &'a mut V {
+        self.or_insert_with(V::default)
+    }
+}
+
+impl<'a, V: Clone> OccupiedEntry<'a, V> {
+    /// Get the key of this entry.
+    #[inline]
+    #[must_use]
+    pub const fn key(&self) -> usize {
+        self.key
+    }
+
+    /// Get a reference to the value.
+    #[inline]
+    #[must_use]
+    pub fn get(&self) -> &V {
+        unsafe { (*self.map.head.add(self.key)).as_ref().unwrap_unchecked() }
+    }
+
+    /// Get a mutable reference to the value.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut V {
+        unsafe { (*self.map.head.add(self.key)).as_mut().unwrap_unchecked() }
+    }
+
+    /// Convert it into a mutable reference to the value, bound to the map.
+    #[inline]
+    #[must_use]
+    pub fn into_mut(self) -> &'a mut V {
+        unsafe { (*self.map.head.add(self.key)).as_mut().unwrap_unchecked() }
+    }
+
+    /// Replace the value, returning the old one.
+    #[inline]
+    pub fn insert(&mut self, v: V) -> V {
+        self.map.stamp(self.key);
+        std::mem::replace(self.get_mut(), v)
+    }
+
+    /// Take the value out of the map.
+    #[inline]
+    #[must_use]
+    pub fn remove(self) -> V {
+        let v = unsafe { ptr::replace(self.map.head.add(self.key), None).unwrap_unchecked() };
+        self.map.narrow(self.key);
+        v
+    }
+}
+
+impl<'a, V: Clone> VacantEntry<'a, V> {
+    /// Get the key of this entry.
+    #[inline]
+    #[must_use]
+    pub const fn key(&self) -> usize {
+        self.key
+    }
+
+    /// Insert the value, returning a mutable reference to it.
+    #[inline]
+    pub fn insert(self, v: V) -> &'a mut V {
+        self.map.insert(self.key, v);
+        unsafe { (*self.map.head.add(self.key)).as_mut().unwrap_unchecked() }
+    }
+}
+
+#[test]
+fn counts_with_entries() {
+    let mut m: Map<u64> = Map::with_capacity_none(4);
+    for k in [1, 2, 1, 1, 3] {
+        m.entry(k).and_modify(|c| *c += 1).or_insert(1);
+    }
+    assert_eq!(3, m[1]);
+    assert_eq!(1, m[2]);
+    assert_eq!(1, m[3]);
+    assert_eq!(3, m.len());
+}
+
+#[test]
+fn inserts_default_via_entry() {
+    let mut m: Map<Vec<u8>> = Map::with_capacity_none(4);
+    m.entry(2).or_default().push(7);
+    m.entry(2).or_default().push(8);
+    assert_eq!(vec![7, 8], m[2]);
+}
+
+#[test]
+fn inserts_lazily_via_entry() {
+    let mut m: Map<u32> = Map::with_capacity_none(4);
+    m.insert(0, 1);
+    assert_eq!(1, *m.entry(0).or_insert_with(|| panic!("not expected")));
+    assert_eq!(5, *m.entry(1).or_insert_with(|| 5));
+}
+
+#[test]
+fn replaces_and_removes_occupied() {
+    let mut m: Map<u32> = Map::with_capacity_none(4);
+    m.insert(3, 1);
+    if let Entry::Occupied(mut e) = m.entry(3) {
+        assert_eq!(3, e.key());
+        assert_eq!(1, e.insert(2));
+        assert_eq!(2, e.remove());
+    }
+    assert!(m.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "The key 4 is over the boundary 4")]
+fn panics_on_entry_over_boundary() {
+    let mut m: Map<u32> = Map::with_capacity_none(4);
+    let _ = m.entry(4).or_insert(1);
+}
+
+#[test]
+fn inserts_value_made_from_key() {
+    let mut m: Map<String> = Map::with_capacity_none(8);
+    for k in [2, 5, 2] {
+        m.entry(k)
+            .or_insert_with_key(|k| format!("node-{k}"))
+            .push('!');
+    }
+    assert_eq!("node-2!!", m[2]);
+    assert_eq!("node-5!", m[5]);
+}
+
+#[test]
+fn reads_key_of_entry() {
+    let mut m: Map<u32> = Map::with_capacity_none(8);
+    m.insert(3, 42);
+    assert_eq!(3, m.entry(3).key());
+    assert_eq!(6, m.entry(6).key());
+    assert!(!m.contains_key(6));
+}
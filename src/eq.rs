@@ -0,0 +1,111 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::Map;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::BuildHasher;
+
+impl<V: Clone + PartialEq> PartialEq for Map<V> {
+    /// Two maps are equal if they have the same keys with equal values,
+    /// no matter what their capacities are.
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<V: Clone + Eq> Eq for Map<V> {}
+
+impl<V: Clone + PartialEq, S: BuildHasher> PartialEq<HashMap<usize, V, S>> for Map<V> {
+    fn eq(&self, other: &HashMap<usize, V, S>) -> bool {
+        self.len() == other.len() && self.iter().all(|(k, v)| other.get(&k) == Some(v))
+    }
+}
+
+impl<V: Clone + PartialEq, S: BuildHasher> PartialEq<Map<V>> for HashMap<usize, V, S> {
+    fn eq(&self, other: &Map<V>) -> bool {
+        other == self
+    }
+}
+
+impl<V: Clone + PartialEq> PartialEq<BTreeMap<usize, V>> for Map<V> {
+    fn eq(&self, other: &BTreeMap<usize, V>) -> bool {
+        self.iter().eq(other.iter().map(|(k, v)| (*k, v)))
+    }
+}
+
+impl<V: Clone + PartialEq> PartialEq<Map<V>> for BTreeMap<usize, V> {
+    fn eq(&self, other: &Map<V>) -> bool {
+        other == self
+    }
+}
+
+#[test]
+fn compares_equal_maps() {
+    let mut a: Map<u32> = Map::with_capacity_none(4);
+    a.insert(1, 10);
+    a.insert(3, 30);
+    let mut b: Map<u32> = Map::with_capacity_none(16);
+    b.insert(3, 30);
+    b.insert(1, 10);
+    assert_eq!(a, b);
+}
+
+#[test]
+fn compares_different_maps() {
+    let mut a: Map<u32> = Map::with_capacity_none(4);
+    a.insert(1, 10);
+    let mut b: Map<u32> = Map::with_capacity_none(4);
+    b.insert(1, 11);
+    assert_ne!(a, b);
+    b.insert(1, 10);
+    b.insert(2, 10);
+    assert_ne!(a, b);
+}
+
+#[test]
+fn compares_with_hash_map() {
+    let mut m: Map<u32> = Map::with_capacity_none(8);
+    m.insert(1, 10);
+    m.insert(5, 50);
+    let mut h: HashMap<usize, u32> = HashMap::new();
+    h.insert(5, 50);
+    h.insert(1, 10);
+    assert_eq!(m, h);
+    assert_eq!(h, m);
+    h.insert(5, 51);
+    assert_ne!(m, h);
+    h.insert(5, 50);
+    h.insert(9, 90);
+    assert_ne!(m, h);
+}
+
+#[test]
+fn compares_with_btree_map() {
+    let mut m: Map<u32> = Map::with_capacity_none(8);
+    m.insert(3, 30);
+    m.insert(0, 0);
+    let mut b: BTreeMap<usize, u32> = BTreeMap::new();
+    b.insert(0, 0);
+    b.insert(3, 30);
+    assert_eq!(m, b);
+    assert_eq!(b, m);
+    b.remove(&3);
+    assert_ne!(m, b);
+}
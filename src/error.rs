@@ -0,0 +1,97 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::{AllocError, GetManyError, MapFullError, OutOfBoundsError};
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+impl Display for MapFullError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("The Map can't hold that many keys")
+    }
+}
+
+impl Error for MapFullError {}
+
+impl Display for OutOfBoundsError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "The key {} is over the boundary {}",
+            self.key, self.capacity
+        )
+    }
+}
+
+impl Error for OutOfBoundsError {}
+
+impl Display for AllocError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::CapacityOverflow => f.write_str("The capacity is too big"),
+            Self::OutOfMemory => f.write_str("Out of memory"),
+        }
+    }
+}
+
+impl Error for AllocError {}
+
+impl Display for GetManyError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Missing(k) => write!(f, "The key {k} is absent"),
+            Self::Overlapping => f.write_str("The keys overlap"),
+        }
+    }
+}
+
+impl Error for GetManyError {}
+
+#[test]
+fn displays_map_full_error() {
+    assert_eq!(
+        "The Map can't hold that many keys",
+        MapFullError.to_string()
+    );
+}
+
+#[test]
+fn displays_alloc_error() {
+    assert_eq!(
+        "The capacity is too big",
+        AllocError::CapacityOverflow.to_string()
+    );
+}
+
+#[test]
+fn displays_out_of_bounds_error() {
+    let e = OutOfBoundsError {
+        key: 5,
+        capacity: 1,
+    };
+    assert_eq!("The key 5 is over the boundary 1", e.to_string());
+}
+
+#[test]
+fn displays_get_many_error() {
+    assert_eq!("The key 3 is absent", GetManyError::Missing(3).to_string());
+    assert_eq!("The keys overlap", GetManyError::Overlapping.to_string());
+}
@@ -0,0 +1,116 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::{Map, OutOfBoundsError};
+
+impl<V: Clone> Extend<(usize, V)> for Map<V> {
+    /// Insert all pairs, one by one, the later ones overwriting
+    /// the earlier ones with the same key.
+    ///
+    /// # Panics
+    ///
+    /// It may panic if any of the keys is outside of the boundary of
+    /// this map, just like [`Map::insert`] does.
+    #[inline]
+    fn extend<I: IntoIterator<Item = (usize, V)>>(&mut self, iter: I) {
+        for (k, v) in iter {
+            self.insert(k, v);
+        }
+    }
+}
+
+impl<V: Clone> Map<V> {
+    /// Insert all pairs, one by one, stopping at the first one whose key
+    /// is outside of the boundary of this map.
+    ///
+    /// Unlike [`Extend::extend`], it never panics on a wrong key, even in
+    /// "debug" mode. The pairs before the rejected one stay inserted,
+    /// while the ones after it are not consumed from the iterator.
+    ///
+    /// # Errors
+    ///
+    /// The rejected pair and the error, if a key is outside of the boundary.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    pub fn try_extend<I: IntoIterator<Item = (usize, V)>>(
+        &mut self,
+        iter: I,
+    ) -> Result<(), (usize, V, OutOfBoundsError)> {
+        for (k, v) in iter {
+            if let Err(e) = self.check_boundaries(k) {
+                return Err((k, v, e));
+            }
+            self.insert(k, v);
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn extends_map() {
+    let mut m: Map<&str> = Map::with_capacity_none(16);
+    m.insert(0, "zero");
+    m.extend([(1, "one"), (5, "five")]);
+    assert_eq!(3, m.len());
+    assert_eq!("five", m[5]);
+}
+
+#[test]
+fn extends_with_duplicate_keys() {
+    let mut m: Map<&str> = Map::with_capacity_none(16);
+    m.extend([(0, "a"), (0, "b")]);
+    assert_eq!(1, m.len());
+    assert_eq!(Some(&"b"), m.get(0));
+}
+
+#[test]
+#[should_panic(expected = "The key 4 is over the boundary 4")]
+#[cfg(debug_assertions)]
+fn extends_beyond_boundary() {
+    let mut m: Map<&str> = Map::with_capacity_none(4);
+    m.extend([(4, "four")]);
+}
+
+#[test]
+fn tries_to_extend_within_boundary() {
+    let mut m: Map<&str> = Map::with_capacity_none(4);
+    assert_eq!(Ok(()), m.try_extend([(0, "a"), (3, "d")]));
+    assert_eq!(2, m.len());
+}
+
+#[test]
+fn stops_extending_at_boundary() {
+    let mut m: Map<&str> = Map::with_capacity_none(4);
+    let mut pairs = vec![(1, "b"), (4, "e"), (2, "c")].into_iter();
+    let (k, v, e) = m.try_extend(&mut pairs).unwrap_err();
+    assert_eq!((4, "e"), (k, v));
+    assert_eq!(
+        OutOfBoundsError {
+            key: 4,
+            capacity: 4
+        },
+        e
+    );
+    assert_eq!(vec![1], m.keys().collect::<Vec<_>>());
+    assert_eq!(Some((2, "c")), pairs.next());
+}
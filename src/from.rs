@@ -0,0 +1,154 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::{AllocError, Map};
+use std::collections::{BTreeMap, HashMap};
+
+impl<V: Clone> TryFrom<Vec<Option<V>>> for Map<V> {
+    type Error = AllocError;
+
+    /// Make a map with the capacity equal to the length of the vector,
+    /// where each `Some` becomes an occupied key and each `None`
+    /// a free one.
+    fn try_from(slots: Vec<Option<V>>) -> Result<Self, Self::Error> {
+        let mut m = Self::try_with_capacity_none(slots.len())?;
+        for (k, v) in slots.into_iter().enumerate() {
+            if let Some(v) = v {
+                m.insert(k, v);
+            }
+        }
+        Ok(m)
+    }
+}
+
+impl<V: Clone> From<&[V]> for Map<V> {
+    /// Make a fully occupied map with the values of the slice.
+    ///
+    /// # Panics
+    ///
+    /// If there is not enough memory, just like [`Map::with_capacity_none`].
+    fn from(values: &[V]) -> Self {
+        let mut m = Self::with_capacity_none(values.len());
+        for (k, v) in values.iter().enumerate() {
+            m.insert(k, v.clone());
+        }
+        m
+    }
+}
+
+impl<V: Clone> Map<V> {
+    /// Make a [`HashMap`] with the keys and clones of the values.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    #[must_use]
+    pub fn to_hashmap(&self) -> HashMap<usize, V> {
+        self.iter().map(|(k, v)| (k, v.clone())).collect()
+    }
+
+    /// Make a [`BTreeMap`] with the keys and clones of the values.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    #[must_use]
+    pub fn to_btreemap(&self) -> BTreeMap<usize, V> {
+        self.iter().map(|(k, v)| (k, v.clone())).collect()
+    }
+
+    /// Turn it into a [`HashMap`], moving the values.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    #[must_use]
+    pub fn into_hashmap(self) -> HashMap<usize, V> {
+        self.into_sorted_vec().into_iter().collect()
+    }
+
+    /// Turn it into a [`BTreeMap`], moving the values.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    #[must_use]
+    pub fn into_btreemap(self) -> BTreeMap<usize, V> {
+        self.into_sorted_vec().into_iter().collect()
+    }
+}
+
+#[test]
+fn makes_map_from_slots() {
+    let slots = vec![None, Some(1), None, Some(3)];
+    let m: Map<u32> = Map::try_from(slots.clone()).unwrap();
+    assert_eq!(4, m.capacity());
+    assert_eq!(vec![1, 3], m.keys().collect::<Vec<_>>());
+    assert_eq!(slots.as_slice(), m.as_option_slice());
+}
+
+#[test]
+fn makes_map_from_empty_slots() {
+    let m: Map<u32> = Map::try_from(vec![]).unwrap();
+    assert_eq!(0, m.capacity());
+    assert!(m.is_empty());
+}
+
+#[test]
+fn makes_map_from_slice() {
+    let m: Map<&str> = Map::from(&["a", "b", "c"][..]);
+    assert_eq!(3, m.len());
+    assert_eq!(3, m.capacity());
+    assert_eq!("c", m[2]);
+}
+
+#[test]
+fn copies_into_std_maps() {
+    let mut m: Map<String> = Map::with_capacity_none(8);
+    m.insert(5, "a".to_string());
+    m.insert(1, "b".to_string());
+    let h = m.to_hashmap();
+    assert_eq!(2, h.len());
+    assert_eq!("a", h[&5]);
+    let b = m.to_btreemap();
+    assert_eq!(vec![1, 5], b.keys().copied().collect::<Vec<_>>());
+    assert_eq!(2, m.len());
+}
+
+#[test]
+fn moves_into_std_maps() {
+    use std::rc::Rc;
+    let v = Rc::new(());
+    let mut m: Map<Rc<()>> = Map::with_capacity_none(8);
+    m.insert(3, Rc::clone(&v));
+    m.insert(0, Rc::clone(&v));
+    let h = m.clone().into_hashmap();
+    assert_eq!(
+        vec![0, 3],
+        m.into_btreemap().into_keys().collect::<Vec<_>>()
+    );
+    assert_eq!(2, h.len());
+    drop(h);
+    assert_eq!(1, Rc::strong_count(&v));
+}
@@ -37,6 +37,22 @@ impl<V: Clone> IndexMut<usize> for Map<V> {
     }
 }
 
+impl<V: Clone> Index<&usize> for Map<V> {
+    type Output = V;
+
+    #[inline]
+    fn index(&self, key: &usize) -> &V {
+        &self[*key]
+    }
+}
+
+impl<V: Clone> IndexMut<&usize> for Map<V> {
+    #[inline]
+    fn index_mut(&mut self, key: &usize) -> &mut V {
+        &mut self[*key]
+    }
+}
+
 #[cfg(test)]
 use std::borrow::Borrow;
 
@@ -56,13 +72,28 @@ fn index_mut() {
 }
 
 #[test]
-#[should_panic]
-fn wrong_index() -> () {
+fn index_by_ref() {
+    let mut m: Map<&str> = Map::with_capacity_none(16);
+    m.insert(1, "first");
+    assert_eq!("first", m[&1]);
+}
+
+#[test]
+fn index_mut_by_ref() {
+    let mut m: Map<i32> = Map::with_capacity_none(16);
+    m.insert(1, 10);
+    m[&1] += 5;
+    assert_eq!(15, m[&1]);
+}
+
+#[test]
+#[should_panic(expected = "No entry found for key")]
+fn wrong_index() {
     let mut m: Map<&str> = Map::with_capacity_none(16);
     m.insert(2, "first");
     m.insert(8, "second");
     m.remove(8);
-    m[8];
+    let _ = m[8];
 }
 
 #[cfg(test)]
@@ -18,22 +18,23 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use crate::{IntoIter, Iter, IterMut, Map};
+use crate::{Drain, IntoIter, Iter, IterMut, Map};
 use std::marker::PhantomData;
+use std::ops::{Bound, RangeBounds};
+use std::ptr;
 
-impl<'a, V: Clone + 'a> Iterator for Iter<'a, V> {
+impl<'a, V: 'a> Iterator for Iter<'a, V> {
     type Item = (usize, &'a V);
 
     /// This is an implementation of the `next` function that returns the next item in an iterator if it
     /// exists, or `None` if the end of the iterator has been reached.
-    /// 
+    ///
     /// Returns:
-    /// 
+    ///
     /// The `next` function returns an `Option` that contains a tuple of `(i, p)` where `i` is the index of
     /// the item and `p` is a reference to the item. If there are no more items to iterate over, it returns
     /// `None`.
     #[inline]
-    #[must_use]
     fn next(&mut self) -> Option<Self::Item> {
         while self.pos < self.max {
             let item = unsafe { &*self.head.add(self.pos) };
@@ -48,11 +49,23 @@ impl<'a, V: Clone + 'a> Iterator for Iter<'a, V> {
     }
 }
 
-impl<'a, V: Clone + 'a> Iterator for IterMut<'a, V> {
+impl<'a, V: 'a> DoubleEndedIterator for Iter<'a, V> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while self.pos < self.max {
+            self.max -= 1;
+            if let Some(p) = unsafe { &*self.head.add(self.max) } {
+                return Some((self.max, p));
+            }
+        }
+        None
+    }
+}
+
+impl<'a, V: 'a> Iterator for IterMut<'a, V> {
     type Item = (usize, &'a mut V);
 
     #[inline]
-    #[must_use]
     fn next(&mut self) -> Option<Self::Item> {
         while self.pos < self.max {
             let item = unsafe { &mut *self.head.add(self.pos) };
@@ -67,11 +80,25 @@ impl<'a, V: Clone + 'a> Iterator for IterMut<'a, V> {
     }
 }
 
+impl<'a, V: 'a> DoubleEndedIterator for IterMut<'a, V> {
+    /// The front and the back cursors never cross, so each slot is
+    /// handed out at most once and the references never alias.
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while self.pos < self.max {
+            self.max -= 1;
+            if let Some(p) = unsafe { &mut *self.head.add(self.max) } {
+                return Some((self.max, p));
+            }
+        }
+        None
+    }
+}
+
 impl<V: Copy> Iterator for IntoIter<V> {
     type Item = (usize, V);
 
     #[inline]
-    #[must_use]
     fn next(&mut self) -> Option<Self::Item> {
         while self.pos < self.max {
             let item = unsafe { &*self.head.add(self.pos) };
@@ -86,7 +113,41 @@ impl<V: Copy> Iterator for IntoIter<V> {
     }
 }
 
-impl<'a, V: Copy> IntoIterator for &'a Map<V> {
+impl<V: Clone> Iterator for Drain<'_, V> {
+    type Item = (usize, V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pos < self.map.max {
+            let i = self.pos;
+            self.pos += 1;
+            if let Some(v) = unsafe { ptr::replace(self.map.head.add(i), None) } {
+                return Some((i, v));
+            }
+        }
+        None
+    }
+}
+
+impl<V: Clone> Drop for Drain<'_, V> {
+    fn drop(&mut self) {
+        self.map.clear();
+    }
+}
+
+impl<V> Clone for Iter<'_, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            max: self.max,
+            pos: self.pos,
+            head: self.head,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<V: Copy> IntoIterator for &Map<V> {
     type Item = (usize, V);
     type IntoIter = IntoIter<V>;
 
@@ -101,6 +162,37 @@ impl<'a, V: Copy> IntoIterator for &'a Map<V> {
 }
 
 impl<V: Clone> Map<V> {
+    /// Make an iterator over the keys present in both maps, pairing
+    /// a mutable value of this map with a value of the other one.
+    ///
+    /// For example, here is how positions may be moved by velocities:
+    ///
+    /// ```
+    /// use emap::Map;
+    /// let mut pos: Map<i32> = Map::with_capacity_none(4);
+    /// pos.insert(0, 10);
+    /// pos.insert(2, 20);
+    /// let mut vel: Map<i32> = Map::with_capacity_none(8);
+    /// vel.insert(2, 5);
+    /// vel.insert(3, 7);
+    /// for (_, p, v) in pos.zip_mut(&vel) {
+    ///     *p += v;
+    /// }
+    /// assert_eq!(25, pos[2]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if any of the maps is not initialized.
+    #[inline]
+    pub fn zip_mut<'a, U: Clone>(
+        &'a mut self,
+        other: &'a Map<U>,
+    ) -> impl Iterator<Item = (usize, &'a mut V, &'a U)> {
+        self.iter_mut()
+            .filter_map(move |(k, v)| other.checked_get(k).ok().flatten().map(|u| (k, v, u)))
+    }
+
     /// Make an iterator over all items.
     ///
     /// # Panics
@@ -108,7 +200,8 @@ impl<V: Clone> Map<V> {
     /// It may panic in debug mode, if the [`Map`] is not initialized.
     #[inline]
     #[must_use]
-    pub const fn iter(&self) -> Iter<V> {
+    #[allow(clippy::iter_without_into_iter)]
+    pub const fn iter(&self) -> Iter<'_, V> {
         #[cfg(debug_assertions)]
         assert!(self.initialized, "Can't iter() non-initialized Map");
         Iter {
@@ -119,6 +212,97 @@ impl<V: Clone> Map<V> {
         }
     }
 
+    /// Call the function for every item, in ascending order of keys,
+    /// reading the slots directly, without an iterator and without
+    /// the "debug" mode checks.
+    ///
+    /// # Safety
+    ///
+    /// The map must be initialized, which it always is, if made by
+    /// [`Map::with_capacity_none`] or [`Map::with_capacity_some`].
+    /// Otherwise, the behavior is undefined.
+    #[inline]
+    pub unsafe fn for_each_occupied_unchecked<F: FnMut(usize, &V)>(&self, mut f: F) {
+        for k in 0..self.max {
+            if let Some(v) = &*self.head.add(k) {
+                f(k, v);
+            }
+        }
+    }
+
+    /// Make an iterator over the items with keys in the range.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use emap::Map;
+    /// let mut m: Map<&str> = Map::with_capacity_none(16);
+    /// m.insert(1, "one");
+    /// m.insert(5, "five");
+    /// m.insert(9, "nine");
+    /// assert_eq!(vec![5], m.iter_range(2..9).map(|(k, _)| k).collect::<Vec<_>>());
+    /// ```
+    ///
+    /// The range is clamped to the boundary of the map, so it's safe to
+    /// use keys beyond the capacity.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    #[must_use]
+    pub fn iter_range<R: RangeBounds<usize>>(&self, range: R) -> Iter<'_, V> {
+        #[cfg(debug_assertions)]
+        assert!(self.initialized, "Can't iter_range() non-initialized Map");
+        let (pos, max) = self.clamp(&range);
+        Iter {
+            max,
+            pos,
+            head: self.head,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Make an iterator over the items, starting from the first key
+    /// that is "greater or equal" than the one provided.
+    ///
+    /// It's handy for paginating over a large map: pass the key after
+    /// the last one seen on the previous page.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    #[must_use]
+    pub fn iter_from(&self, start: usize) -> Iter<'_, V> {
+        self.iter_range(start..)
+    }
+
+    /// Turn the range into a pair of starting and ending positions, that
+    /// are inside the used part of the map.
+    #[inline]
+    pub(crate) fn clamp<R: RangeBounds<usize>>(&self, range: &R) -> (usize, usize) {
+        let (start, end) = Self::bounds(range);
+        (start, end.min(self.max))
+    }
+
+    /// Turn the range into a pair of starting and ending positions,
+    /// where the end is exclusive.
+    #[inline]
+    pub(crate) fn bounds<R: RangeBounds<usize>>(range: &R) -> (usize, usize) {
+        let start = match range.start_bound() {
+            Bound::Included(s) => *s,
+            Bound::Excluded(s) => s.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(e) => e.saturating_add(1),
+            Bound::Excluded(e) => *e,
+            Bound::Unbounded => usize::MAX,
+        };
+        (start, end)
+    }
+
     /// Make a mutable iterator over all items.
     ///
     /// For example:
@@ -140,7 +324,8 @@ impl<V: Clone> Map<V> {
     /// It may panic in debug mode, if the [`Map`] is not initialized.
     #[inline]
     #[must_use]
-    pub const fn iter_mut(&self) -> IterMut<V> {
+    #[allow(clippy::iter_without_into_iter)]
+    pub const fn iter_mut(&mut self) -> IterMut<'_, V> {
         #[cfg(debug_assertions)]
         assert!(self.initialized, "Can't iter_mut() non-initialized Map");
         IterMut {
@@ -151,6 +336,56 @@ impl<V: Clone> Map<V> {
         }
     }
 
+    /// Make an iterator that moves all items out, in ascending order
+    /// of keys, leaving the map empty, but with its capacity intact.
+    ///
+    /// If the iterator is dropped before it is fully consumed, the rest
+    /// of the values are dropped.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    pub const fn drain(&mut self) -> Drain<'_, V> {
+        #[cfg(debug_assertions)]
+        assert!(self.initialized, "Can't drain() non-initialized Map");
+        Drain {
+            pos: self.min,
+            map: self,
+        }
+    }
+
+    /// Make two mutable iterators at once: over the items with keys below
+    /// the pivot and over the items with keys equal to or above it.
+    ///
+    /// The iterators cover disjoint slots, so they may be used together,
+    /// for example in divide-and-conquer algorithms.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    #[must_use]
+    pub fn split_around(&mut self, pivot: usize) -> (IterMut<'_, V>, IterMut<'_, V>) {
+        #[cfg(debug_assertions)]
+        assert!(self.initialized, "Can't split_around() non-initialized Map");
+        let mid = pivot.min(self.max);
+        (
+            IterMut {
+                max: mid,
+                pos: 0,
+                head: self.head,
+                _marker: PhantomData,
+            },
+            IterMut {
+                max: self.max,
+                pos: mid,
+                head: self.head,
+                _marker: PhantomData,
+            },
+        )
+    }
+
     /// Make an iterator over all items.
     ///
     /// # Panics
@@ -227,6 +462,51 @@ fn iterate_without_function() {
     assert_eq!(1, count);
 }
 
+#[cfg(test)]
+fn sparse() -> Map<&'static str> {
+    let mut m: Map<&str> = Map::with_capacity_none(16);
+    m.insert(1, "one");
+    m.insert(4, "four");
+    m.insert(6, "six");
+    m.insert(11, "eleven");
+    m
+}
+
+#[test]
+fn iterates_half_open_range() {
+    let m = sparse();
+    let keys: Vec<usize> = m.iter_range(4..11).map(|(k, _)| k).collect();
+    assert_eq!(vec![4, 6], keys);
+}
+
+#[test]
+fn iterates_inclusive_range() {
+    let m = sparse();
+    let keys: Vec<usize> = m.iter_range(4..=11).map(|(k, _)| k).collect();
+    assert_eq!(vec![4, 6, 11], keys);
+}
+
+#[test]
+fn iterates_range_beyond_boundary() {
+    let m = sparse();
+    let keys: Vec<usize> = m.iter_range(5..100).map(|(k, _)| k).collect();
+    assert_eq!(vec![6, 11], keys);
+    assert!(m.iter_range(50..).next().is_none());
+    assert_eq!(4, m.iter_range(..=usize::MAX).count());
+}
+
+#[test]
+fn clones_iterator() {
+    let m = sparse();
+    let mut a = m.iter();
+    a.next();
+    let mut b = a.clone();
+    assert_eq!(Some((4, &"four")), a.next());
+    assert_eq!(Some((4, &"four")), b.next());
+    assert_eq!(2, a.count());
+    assert_eq!(2, b.count());
+}
+
 #[test]
 fn iterate_and_mutate() {
     let mut m: Map<u64> = Map::with_capacity_none(16);
@@ -242,3 +522,160 @@ fn iterate_and_mutate() {
     }
     assert_eq!(115, sum);
 }
+
+#[test]
+fn iterates_backwards() {
+    let m = sparse();
+    let keys: Vec<usize> = m.iter().rev().map(|(k, _)| k).collect();
+    assert_eq!(vec![11, 6, 4, 1], keys);
+}
+
+#[test]
+fn jumps_to_nth_item() {
+    let m = sparse();
+    assert_eq!(Some((6, &"six")), m.iter().nth(2));
+    let mut it = m.iter();
+    assert_eq!(Some((4, &"four")), it.nth(1));
+    assert_eq!(Some((11, &"eleven")), it.nth(1));
+    assert_eq!(None, it.next());
+    assert_eq!(None, m.iter().nth(4));
+}
+
+#[test]
+fn zips_intersection_only() {
+    let mut m = sparse();
+    let mut other: Map<usize> = Map::with_capacity_none(8);
+    other.insert(0, 0);
+    other.insert(4, 4);
+    other.insert(6, 6);
+    other.insert(7, 7);
+    let mut seen = vec![];
+    for (k, v, u) in m.zip_mut(&other) {
+        seen.push((k, *u));
+        *v = "zipped";
+    }
+    assert_eq!(vec![(4, 4), (6, 6)], seen);
+    assert_eq!("zipped", m[6]);
+    assert_eq!("one", m[1]);
+}
+
+#[test]
+fn mutates_from_both_ends() {
+    let mut m: Map<u32> = Map::with_capacity_none(16);
+    for k in [1, 3, 4, 8, 12] {
+        m.insert(k, 0);
+    }
+    let mut it = m.iter_mut();
+    let mut step = 0;
+    loop {
+        step += 1;
+        let item = if step % 2 == 0 {
+            it.next()
+        } else {
+            it.next_back()
+        };
+        match item {
+            Some((_, v)) => *v += step,
+            None => break,
+        }
+    }
+    assert_eq!(vec![2, 4, 5, 3, 1], m.values().copied().collect::<Vec<_>>());
+}
+
+#[test]
+fn iterates_from_key() {
+    let m = sparse();
+    let keys: Vec<usize> = m.iter_from(3).map(|(k, _)| k).collect();
+    assert_eq!(vec![4, 6, 11], keys);
+    assert!(m.iter_from(0).eq(m.iter()));
+    assert_eq!(0, m.iter_from(100).count());
+}
+
+#[test]
+fn paginates_with_iter_from() {
+    let m = sparse();
+    let page: Vec<usize> = m.iter_from(0).take(2).map(|(k, _)| k).collect();
+    assert_eq!(vec![1, 4], page);
+    let next: Vec<usize> = m.iter_from(page[1] + 1).take(2).map(|(k, _)| k).collect();
+    assert_eq!(vec![6, 11], next);
+}
+
+#[test]
+fn drops_rest_of_partially_consumed_drain() {
+    use std::rc::Rc;
+    let v = Rc::new(());
+    let mut m: Map<Rc<()>> = Map::with_capacity_none(8);
+    for k in [1, 3, 5, 7] {
+        m.insert(k, Rc::clone(&v));
+    }
+    let mut it = m.drain();
+    let first = it.next();
+    assert_eq!(Some(1), first.as_ref().map(|(k, _)| *k));
+    drop(it);
+    assert_eq!(2, Rc::strong_count(&v));
+    assert!(m.is_empty());
+    drop(first);
+    assert_eq!(1, Rc::strong_count(&v));
+}
+
+#[test]
+fn moves_each_value_out_of_drain_once() {
+    use std::rc::Rc;
+    let v = Rc::new(());
+    let mut m: Map<Rc<()>> = Map::with_capacity_none(8);
+    for k in [0, 2, 6] {
+        m.insert(k, Rc::clone(&v));
+    }
+    let items: Vec<(usize, Rc<()>)> = m.drain().collect();
+    assert_eq!(
+        vec![0, 2, 6],
+        items.iter().map(|(k, _)| *k).collect::<Vec<_>>()
+    );
+    assert_eq!(4, Rc::strong_count(&v));
+    drop(items);
+    assert_eq!(1, Rc::strong_count(&v));
+    assert_eq!(0, m.len());
+}
+
+#[test]
+fn keeps_values_after_partial_iteration() {
+    let mut m: Map<u32> = Map::with_capacity_none(8);
+    m.insert(2, 42);
+    m.insert(4, 43);
+    assert_eq!(Some((2, 42)), m.into_iter().next());
+    assert_eq!(Some((2, &42)), m.iter().next());
+    assert_eq!(2, m.len());
+    assert_eq!(43, m[4]);
+}
+
+#[test]
+fn visits_same_items_unchecked() {
+    let m = sparse();
+    let mut seen = vec![];
+    unsafe {
+        m.for_each_occupied_unchecked(|k, v| seen.push((k, *v)));
+    }
+    assert_eq!(m.iter().map(|(k, v)| (k, *v)).collect::<Vec<_>>(), seen);
+}
+
+#[test]
+fn splits_around_pivot() {
+    let mut m: Map<u32> = Map::with_capacity_none(16);
+    for k in [1, 4, 6, 11] {
+        m.insert(k, 0);
+    }
+    let (lower, upper) = m.split_around(6);
+    let mut seen = vec![];
+    for ((a, x), (b, y)) in lower.zip(upper) {
+        *x += 1;
+        *y += 1;
+        seen.push(a);
+        seen.push(b);
+    }
+    seen.sort_unstable();
+    assert_eq!(vec![1, 4, 6, 11], seen);
+    assert!(m.values().all(|v| *v == 1));
+    let (lower, upper) = m.split_around(100);
+    assert_eq!(4, lower.count());
+    assert_eq!(0, upper.count());
+}
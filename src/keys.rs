@@ -20,16 +20,15 @@
 
 use crate::Keys;
 use crate::Map;
-use std::ptr;
+use std::ops::RangeBounds;
 
 impl<V> Iterator for Keys<V> {
     type Item = usize;
 
     #[inline]
-    #[must_use]
     fn next(&mut self) -> Option<usize> {
         while self.pos < self.max {
-            let opt = unsafe { ptr::read(self.head.add(self.pos)) };
+            let opt = unsafe { &*self.head.add(self.pos) };
             if opt.is_some() {
                 let k = self.pos;
                 self.pos += 1;
@@ -41,6 +40,39 @@ impl<V> Iterator for Keys<V> {
     }
 }
 
+impl<V> DoubleEndedIterator for Keys<V> {
+    #[inline]
+    fn next_back(&mut self) -> Option<usize> {
+        while self.pos < self.max {
+            self.max -= 1;
+            if unsafe { &*self.head.add(self.max) }.is_some() {
+                return Some(self.max);
+            }
+        }
+        None
+    }
+}
+
+impl<V> Keys<V> {
+    /// Collect the remaining keys into a vector, in ascending order.
+    #[inline]
+    #[must_use]
+    pub fn as_vec(self) -> Vec<usize> {
+        self.collect()
+    }
+}
+
+impl<V> Clone for Keys<V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            max: self.max,
+            pos: self.pos,
+            head: self.head,
+        }
+    }
+}
+
 impl<V: Clone> Map<V> {
     /// Make an iterator over all keys.
     ///
@@ -58,6 +90,194 @@ impl<V: Clone> Map<V> {
             head: self.head,
         }
     }
+
+    /// Make an iterator over the keys in the range.
+    ///
+    /// The range is clamped to the boundary of the map.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    #[must_use]
+    pub fn keys_range<R: RangeBounds<usize>>(&self, range: R) -> Keys<V> {
+        #[cfg(debug_assertions)]
+        assert!(self.initialized, "Can't keys_range() non-initialized Map");
+        let (pos, max) = self.clamp(&range);
+        Keys {
+            max,
+            pos,
+            head: self.head,
+        }
+    }
+
+    /// Get the occupancy bitmap, where the bit `k` is set if the key `k`
+    /// is occupied.
+    ///
+    /// The bit `k` is the bit `k % 64` of the word `k / 64`, and there are
+    /// just enough words to cover the capacity, so the bitmaps of two maps
+    /// of the same capacity can be combined word by word.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    #[must_use]
+    pub fn keys_bitset(&self) -> Vec<u64> {
+        let mut bits = vec![0; self.capacity().div_ceil(64)];
+        for k in self.keys() {
+            bits[k / 64] |= 1 << (k % 64);
+        }
+        bits
+    }
+
+    /// Make an iterator over the keys present in both maps.
+    ///
+    /// The maps may have different capacities.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if any of the maps is not initialized.
+    #[inline]
+    pub fn common_keys<'a, U: Clone>(
+        &'a self,
+        other: &'a Map<U>,
+    ) -> impl Iterator<Item = usize> + 'a {
+        #[cfg(debug_assertions)]
+        assert!(
+            self.initialized && other.initialized,
+            "Can't common_keys() non-initialized Map"
+        );
+        self.keys()
+            .filter(move |k| matches!(other.checked_get(*k), Ok(Some(_))))
+    }
+
+    /// Make an iterator over the keys present in this map, but absent
+    /// in the other one.
+    ///
+    /// The maps may have different capacities.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if any of the maps is not initialized.
+    #[inline]
+    pub fn only_self_keys<'a, U: Clone>(
+        &'a self,
+        other: &'a Map<U>,
+    ) -> impl Iterator<Item = usize> + 'a {
+        #[cfg(debug_assertions)]
+        assert!(
+            self.initialized && other.initialized,
+            "Can't only_self_keys() non-initialized Map"
+        );
+        self.keys()
+            .filter(move |k| !matches!(other.checked_get(*k), Ok(Some(_))))
+    }
+
+    /// Get the key of the `n`-th occupied slot, counting from zero in
+    /// ascending order of keys.
+    ///
+    /// It walks the slots from the bottom, so the cost is proportional to
+    /// the key returned.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    #[must_use]
+    pub fn nth_key(&self, n: usize) -> Option<usize> {
+        self.keys().nth(n)
+    }
+
+    /// Collect the keys into a boxed slice, in ascending order.
+    ///
+    /// It takes less space than a [`Vec`], so it fits better for keeping
+    /// the keys for a long time.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    #[must_use]
+    pub fn occupied_keys_boxed(&self) -> Box<[usize]> {
+        self.keys().collect()
+    }
+
+    /// Check whether all the keys provided are present in the map.
+    ///
+    /// The keys outside of the boundary are treated as absent, without
+    /// a panic. It stops at the first absent key.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    #[must_use]
+    pub fn contains_all_keys(&self, keys: &[usize]) -> bool {
+        #[cfg(debug_assertions)]
+        assert!(
+            self.initialized,
+            "Can't contains_all_keys() non-initialized Map"
+        );
+        keys.iter()
+            .all(|k| matches!(self.checked_get(*k), Ok(Some(_))))
+    }
+
+    /// Check whether at least one of the keys provided is present
+    /// in the map.
+    ///
+    /// The keys outside of the boundary are treated as absent, without
+    /// a panic. It stops at the first present key.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    #[must_use]
+    pub fn contains_any_key(&self, keys: &[usize]) -> bool {
+        #[cfg(debug_assertions)]
+        assert!(
+            self.initialized,
+            "Can't contains_any_key() non-initialized Map"
+        );
+        keys.iter()
+            .any(|k| matches!(self.checked_get(*k), Ok(Some(_))))
+    }
+
+    /// Get the smallest key in the map.
+    ///
+    /// The map keeps the bounds of its used range of keys tight in every
+    /// method that adds or removes values, so it takes constant time.
+    /// Only if a closure panics in the middle of a bulk method, like
+    /// [`Map::retain_map`], the bounds may stay loose, and then the empty
+    /// keys above the bound are scanned.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    #[must_use]
+    pub fn first_key(&self) -> Option<usize> {
+        #[cfg(debug_assertions)]
+        assert!(self.initialized, "Can't first_key() non-initialized Map");
+        (self.min..self.max).find(|k| self.contains_key(*k))
+    }
+
+    /// Get the largest key in the map.
+    ///
+    /// Just like [`Map::first_key`], it usually takes constant time,
+    /// since the top of the used range is kept up to date.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    #[must_use]
+    pub fn last_key(&self) -> Option<usize> {
+        #[cfg(debug_assertions)]
+        assert!(self.initialized, "Can't last_key() non-initialized Map");
+        (self.min..self.max).rev().find(|k| self.contains_key(*k))
+    }
 }
 
 #[test]
@@ -74,3 +294,255 @@ fn insert_and_jump_over_next_key() {
     assert_eq!(0, keys.next().unwrap());
     assert!(keys.next().is_none());
 }
+
+#[test]
+fn finds_first_and_last_keys() {
+    let mut m: Map<&str> = Map::with_capacity_none(16);
+    m.insert(3, "three");
+    m.insert(7, "seven");
+    m.insert(1, "one");
+    assert_eq!(Some(1), m.first_key());
+    assert_eq!(Some(7), m.last_key());
+}
+
+#[test]
+fn finds_first_and_last_keys_after_removing_them() {
+    let mut m: Map<&str> = Map::with_capacity_none(16);
+    for k in [2, 6, 9, 13] {
+        m.insert(k, "x");
+    }
+    m.remove(2);
+    m.remove(13);
+    assert_eq!(Some(6), m.first_key());
+    assert_eq!(Some(9), m.last_key());
+    m.insert(0, "y");
+    m.remove(9);
+    assert_eq!(Some(0), m.first_key());
+    assert_eq!(Some(6), m.last_key());
+    m.clear();
+    assert_eq!(None, m.first_key());
+}
+
+#[test]
+fn keeps_bounds_of_used_range() {
+    let mut m: Map<&str> = Map::with_capacity_none(16);
+    for k in [2, 6, 9, 13] {
+        m.insert(k, "x");
+    }
+    assert_eq!((2, 14), (m.min, m.max));
+    m.remove(2);
+    assert_eq!((6, 14), (m.min, m.max));
+    m.remove(13);
+    assert_eq!((6, 10), (m.min, m.max));
+    m.remove(9);
+    m.remove(6);
+    assert!(m.min >= m.max);
+    assert_eq!(None, m.first_key());
+    m.insert(4, "y");
+    assert_eq!((4, 5), (m.min, m.max));
+    assert_eq!(Some(4), m.last_key());
+}
+
+#[test]
+fn tightens_bounds_after_bulk_removal() {
+    let mut m: Map<u32> = Map::with_capacity_none(16);
+    for k in [1, 4, 7, 11, 14] {
+        m.insert(k, u32::try_from(k).unwrap());
+    }
+    m.retain(|_, v| *v != 1 && *v != 14);
+    assert_eq!((4, 12), (m.min, m.max));
+    assert_eq!(vec![4], m.drain_keys(|v| *v < 5));
+    assert_eq!((7, 12), (m.min, m.max));
+    m.truncate_keys(10);
+    assert_eq!((7, 8), (m.min, m.max));
+    m.insert(9, 9);
+    if let crate::Entry::Occupied(e) = m.entry(9) {
+        assert_eq!(9, e.remove());
+    }
+    assert_eq!((7, 8), (m.min, m.max));
+    assert_eq!(Some(7), m.slot(7).take());
+    assert!(m.min >= m.max);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "Can't common_keys() non-initialized Map")]
+fn refuses_common_keys_with_non_initialized_map() {
+    let m: Map<u8> = Map::with_capacity_none(4);
+    let other: Map<u8> = Map::with_capacity(4);
+    assert_eq!(0, m.common_keys(&other).count());
+}
+
+#[test]
+fn finds_no_keys_in_empty_map() {
+    let mut m: Map<&str> = Map::with_capacity_none(16);
+    assert_eq!(None, m.first_key());
+    assert_eq!(None, m.last_key());
+    m.insert(5, "five");
+    m.remove(5);
+    assert_eq!(None, m.first_key());
+    assert_eq!(None, m.last_key());
+}
+
+#[test]
+fn takes_keys_in_range() {
+    let mut m: Map<&str> = Map::with_capacity_none(16);
+    m.insert(1, "one");
+    m.insert(3, "three");
+    m.insert(8, "eight");
+    assert_eq!(vec![3, 8], m.keys_range(2..=8).collect::<Vec<usize>>());
+    assert_eq!(vec![1], m.keys_range(..3).collect::<Vec<usize>>());
+}
+
+#[test]
+fn clones_keys_iterator() {
+    let mut m: Map<&str> = Map::with_capacity_none(16);
+    m.insert(1, "one");
+    m.insert(4, "four");
+    let mut a = m.keys();
+    let mut b = a.clone();
+    assert_eq!(Some(1), a.next());
+    assert_eq!(Some(4), a.next());
+    assert_eq!(Some(1), b.next());
+}
+
+#[test]
+fn iterates_keys_backwards() {
+    let mut m: Map<u32> = Map::with_capacity_none(16);
+    m.insert(1, 42);
+    m.insert(4, 42);
+    m.insert(9, 42);
+    assert_eq!(vec![9, 4, 1], m.keys().rev().collect::<Vec<_>>());
+}
+
+#[test]
+fn meets_in_the_middle_of_keys() {
+    let mut m: Map<u32> = Map::with_capacity_none(16);
+    m.insert(1, 42);
+    m.insert(4, 42);
+    m.insert(9, 42);
+    let mut keys = m.keys();
+    assert_eq!(Some(9), keys.next_back());
+    assert_eq!(Some(1), keys.next());
+    assert_eq!(Some(4), keys.next_back());
+    assert_eq!(None, keys.next());
+    assert_eq!(None, keys.next_back());
+}
+
+#[test]
+fn collects_keys_to_vec() {
+    let mut m: Map<Vec<u8>> = Map::with_capacity_none(16);
+    m.insert(2, vec![1]);
+    m.insert(5, vec![2]);
+    assert_eq!(vec![2, 5], m.keys().as_vec());
+    assert_eq!(vec![1], m[2]);
+}
+
+#[test]
+fn jumps_to_nth_key() {
+    let mut m: Map<u32> = Map::with_capacity_none(16);
+    m.insert(9, 42);
+    m.insert(1, 42);
+    m.insert(4, 42);
+    assert_eq!(Some(9), m.keys().nth(2));
+    assert_eq!(None, m.keys().nth(3));
+}
+
+#[test]
+fn exports_keys_bitset() {
+    let mut m: Map<u32> = Map::with_capacity_none(130);
+    for k in [0, 5, 63, 64, 129] {
+        m.insert(k, 42);
+    }
+    m.remove(5);
+    let bits = m.keys_bitset();
+    assert_eq!(3, bits.len());
+    let keys: Vec<usize> = (0..bits.len() * 64)
+        .filter(|k| bits[k / 64] & (1 << (k % 64)) != 0)
+        .collect();
+    assert_eq!(m.keys().collect::<Vec<_>>(), keys);
+}
+
+#[test]
+fn exports_empty_bitset() {
+    let m: Map<u32> = Map::with_capacity_none(0);
+    assert!(m.keys_bitset().is_empty());
+}
+
+#[cfg(test)]
+fn keyed(cap: usize, keys: &[usize]) -> Map<u8> {
+    let mut m = Map::with_capacity_none(cap);
+    for k in keys {
+        m.insert(*k, 0);
+    }
+    m
+}
+
+#[test]
+fn finds_common_keys() {
+    let a = keyed(8, &[1, 3, 5, 7]);
+    let b = keyed(8, &[0, 3, 4, 7]);
+    assert_eq!(vec![3, 7], a.common_keys(&b).collect::<Vec<_>>());
+    assert_eq!(vec![1, 5], a.only_self_keys(&b).collect::<Vec<_>>());
+}
+
+#[test]
+fn finds_no_common_keys_in_disjoint_maps() {
+    let a = keyed(8, &[1, 3]);
+    let b = keyed(8, &[0, 2]);
+    assert_eq!(0, a.common_keys(&b).count());
+    assert_eq!(vec![1, 3], a.only_self_keys(&b).collect::<Vec<_>>());
+}
+
+#[test]
+fn compares_keys_of_different_capacities() {
+    let a = keyed(16, &[1, 3, 12]);
+    let b = keyed(4, &[3]);
+    assert_eq!(vec![3], a.common_keys(&b).collect::<Vec<_>>());
+    assert_eq!(vec![1, 12], a.only_self_keys(&b).collect::<Vec<_>>());
+    assert_eq!(vec![3], b.common_keys(&a).collect::<Vec<_>>());
+    assert_eq!(0, b.only_self_keys(&a).count());
+}
+
+#[test]
+fn finds_nth_key() {
+    let mut m: Map<u32> = Map::with_capacity_none(16);
+    for k in [12, 3, 7] {
+        m.insert(k, 42);
+    }
+    assert_eq!(Some(3), m.nth_key(0));
+    assert_eq!(Some(12), m.nth_key(m.len() - 1));
+    assert_eq!(None, m.nth_key(m.len()));
+}
+
+#[test]
+fn checks_presence_of_all_keys() {
+    let mut m: Map<u32> = Map::with_capacity_none(8);
+    m.insert(1, 1);
+    m.insert(4, 4);
+    assert!(m.contains_all_keys(&[4, 1]));
+    assert!(m.contains_all_keys(&[]));
+    assert!(!m.contains_all_keys(&[1, 2]));
+    assert!(!m.contains_all_keys(&[1, 100]));
+}
+
+#[test]
+fn checks_presence_of_any_key() {
+    let mut m: Map<u32> = Map::with_capacity_none(8);
+    m.insert(1, 1);
+    assert!(m.contains_any_key(&[0, 1]));
+    assert!(!m.contains_any_key(&[]));
+    assert!(!m.contains_any_key(&[0, 2]));
+    assert!(!m.contains_any_key(&[100, usize::MAX]));
+}
+
+#[test]
+fn boxes_occupied_keys() {
+    let mut m: Map<u32> = Map::with_capacity_none(16);
+    for k in [9, 2, 14] {
+        m.insert(k, 42);
+    }
+    let keys = m.occupied_keys_boxed();
+    assert_eq!(m.keys().collect::<Vec<_>>().as_slice(), &*keys);
+    assert_eq!(&[2, 9, 14], &*keys);
+}
@@ -40,28 +40,176 @@
 #![allow(clippy::multiple_inherent_impl)]
 #![allow(clippy::multiple_crate_versions)]
 
+mod array_map;
 mod clone;
 mod ctors;
 mod debug;
+#[cfg(feature = "serde")]
+mod dense;
+mod entry;
+mod eq;
+mod error;
+mod extend;
+mod from;
 mod index;
 mod iterators;
 mod keys;
 mod map;
 mod next_key;
+mod ord;
+#[cfg(feature = "ordered")]
+mod ordered;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod raw;
 #[cfg(feature = "serde")]
 mod serialization;
+mod slot;
+mod slots;
+mod stream;
 mod values;
+mod view;
 
 use std::alloc::Layout;
 use std::marker::PhantomData;
 
 /// A map with a fixed capacity and `usize` as keys.
 pub struct Map<V> {
+    min: usize,
     max: usize,
     head: *mut Option<V>,
     layout: Layout,
     #[cfg(debug_assertions)]
     initialized: bool,
+    #[cfg(feature = "ordered")]
+    seqs: Vec<u64>,
+    #[cfg(feature = "ordered")]
+    seq: u64,
+}
+
+// SAFETY: The map uniquely owns the memory behind `head`, just like
+// `Box<[Option<V>]>` does, and never shares the pointer with anything else.
+// Sending the map to another thread moves the values along with it, which
+// is fine as long as `V` is `Send`.
+unsafe impl<V: Send> Send for Map<V> {}
+
+// SAFETY: The values are only reachable through `&self` as `&V` and through
+// `&mut self` as `&mut V`, so sharing the map between threads shares
+// nothing but `&V`, which is fine as long as `V` is `Sync`.
+unsafe impl<V: Sync> Sync for Map<V> {}
+
+/// A map with a fixed capacity `N`, which keeps its values inline,
+/// without any heap allocation.
+///
+/// It works on its slots exactly the way [`Map`] does, so, for example,
+/// [`ArrayMap::insert`] overwrites a value without dropping it. The
+/// crate depends on `std`, so it can't be used in `no_std` builds.
+///
+/// For example:
+///
+/// ```
+/// use emap::ArrayMap;
+/// let mut m: ArrayMap<&str, 4> = ArrayMap::new();
+/// m.insert(2, "Hello, world!");
+/// assert_eq!(0, m.push("Good bye!"));
+/// assert_eq!(2, m.len());
+/// ```
+pub struct ArrayMap<V, const N: usize> {
+    max: usize,
+    slots: [Option<V>; N],
+}
+
+/// The error returned when a [`Map`] can't hold the requested keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MapFullError;
+
+/// The error returned when a key is outside of the boundary of a [`Map`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutOfBoundsError {
+    /// The key that was requested.
+    pub key: usize,
+    /// The capacity of the map.
+    pub capacity: usize,
+}
+
+/// The error returned when a [`Map`] can't allocate its memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AllocError {
+    /// The capacity is too big for a memory layout.
+    CapacityOverflow,
+    /// The allocator failed to provide the memory.
+    OutOfMemory,
+}
+
+/// The error returned by [`Map::get_many_mut`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GetManyError {
+    /// The key is absent or outside of the boundary.
+    Missing(usize),
+    /// The same key was requested more than once.
+    Overlapping,
+}
+
+/// A single operation on a [`Map`], for model-based testing
+/// through [`Map::apply_op`].
+#[doc(hidden)]
+#[derive(Debug, Clone)]
+pub enum Op<V> {
+    /// Call [`Map::insert`].
+    Insert(usize, V),
+    /// Call [`Map::remove`].
+    Remove(usize),
+    /// Call [`Map::push`].
+    Push(V),
+    /// Call [`Map::clear`].
+    Clear,
+}
+
+/// A wrapper around a [`Map`], which serializes it as a plain sequence
+/// of values, when the map is fully occupied.
+///
+/// The keys are dropped in this case, since they are just the positions
+/// of the values. When there are gaps, it falls back to the key-value
+/// representation of the [`Map`] itself.
+#[cfg(feature = "serde")]
+pub struct Dense<V>(pub Map<V>);
+
+/// A view into a single key of a [`Map`], which is either occupied or vacant.
+///
+/// It is created by [`Map::entry`].
+pub enum Entry<'a, V> {
+    /// The key has a value.
+    Occupied(OccupiedEntry<'a, V>),
+    /// The key has no value.
+    Vacant(VacantEntry<'a, V>),
+}
+
+/// A view into an occupied key of a [`Map`].
+pub struct OccupiedEntry<'a, V> {
+    key: usize,
+    map: &'a mut Map<V>,
+}
+
+/// A view into a vacant key of a [`Map`].
+pub struct VacantEntry<'a, V> {
+    key: usize,
+    map: &'a mut Map<V>,
+}
+
+/// A handle to a single key of a [`Map`], for repeated access
+/// without checking the boundary again.
+///
+/// It is created by [`Map::slot`] and borrows the map mutably, so
+/// the map can't be touched otherwise while the handle is alive.
+pub struct Slot<'a, V> {
+    key: usize,
+    map: &'a mut Map<V>,
+}
+
+/// A read-only view of a [`Map`], which checks that the map is
+/// initialized only once, when it is created by [`Map::view`].
+pub struct MapView<'a, V> {
+    map: &'a Map<V>,
 }
 
 /// Iterator over the [`Map`].
@@ -87,6 +235,15 @@ pub struct IntoIter<V> {
     head: *mut Option<V>,
 }
 
+/// Draining iterator over the [`Map`], which moves the values out.
+///
+/// It is created by [`Map::drain`]. When it is dropped, the values
+/// that were not yielded are dropped too, and the map is left empty.
+pub struct Drain<'a, V: Clone> {
+    pos: usize,
+    map: &'a mut Map<V>,
+}
+
 /// Iterator over the values of a [`Map`].
 pub struct Values<'a, V> {
     max: usize,
@@ -95,6 +252,14 @@ pub struct Values<'a, V> {
     _marker: PhantomData<&'a V>,
 }
 
+/// Mutable iterator over the values of a [`Map`].
+pub struct ValuesMut<'a, V> {
+    max: usize,
+    pos: usize,
+    head: *mut Option<V>,
+    _marker: PhantomData<&'a V>,
+}
+
 /// Into-iterator over the values of a [`Map`].
 pub struct IntoValues<V> {
     max: usize,
@@ -112,6 +277,26 @@ pub struct Keys<V> {
 #[cfg(test)]
 use std::time::Instant;
 
+#[cfg(test)]
+const fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn is_send_and_sync() {
+    assert_send_sync::<Map<u64>>();
+    assert_send_sync::<Map<String>>();
+}
+
+#[test]
+fn moves_between_threads() {
+    use std::sync::{Arc, Mutex};
+    let m = Arc::new(Mutex::new(Map::with_capacity_none(4)));
+    let other = Arc::clone(&m);
+    std::thread::spawn(move || other.lock().unwrap().insert(1, 42))
+        .join()
+        .unwrap();
+    assert_eq!(42, m.lock().unwrap()[1]);
+}
+
 /// Run it like this from command line:
 ///
 /// ```text
@@ -18,7 +18,9 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use crate::Map;
+use crate::{raw, GetManyError, Map, Op, OutOfBoundsError};
+use std::mem;
+use std::ops::ControlFlow;
 use std::ptr;
 
 impl<V: Clone> Map<V> {
@@ -39,13 +41,56 @@ impl<V: Clone> Map<V> {
     pub fn len(&self) -> usize {
         #[cfg(debug_assertions)]
         assert!(self.initialized, "Can't do len() on non-initialized Map");
-        let mut busy = 0;
-        for i in 0..self.max {
-            if self.get(i).is_some() {
-                busy += 1;
-            }
+        unsafe { raw::len(self.head, self.max) }
+    }
+
+    /// Return the share of occupied keys, from `0.0` to `1.0`.
+    ///
+    /// It is `0.0` for a map with zero capacity.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn load_factor(&self) -> f64 {
+        if self.capacity() == 0 {
+            return 0.0;
         }
-        busy
+        self.len() as f64 / self.capacity() as f64
+    }
+
+    /// Return the share of empty slots between the smallest and the
+    /// largest keys, from `0.0` to `1.0`.
+    ///
+    /// It is `0.0` if all the keys in between are occupied, or if the map
+    /// is empty, and it grows as the keys get scattered. A high value
+    /// suggests calling [`Map::compact_keys`].
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn fragmentation(&self) -> f64 {
+        let (Some(first), Some(last)) = (self.first_key(), self.last_key()) else {
+            return 0.0;
+        };
+        let span = last - first + 1;
+        (span - self.len()) as f64 / span as f64
+    }
+
+    /// Return the number of bytes taken by the occupied keys.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    #[must_use]
+    pub fn occupied_bytes(&self) -> usize {
+        self.len() * mem::size_of::<Option<V>>()
     }
 
     /// Does the map contain this key?
@@ -61,26 +106,40 @@ impl<V: Clone> Map<V> {
     #[allow(clippy::missing_const_for_fn)]
     pub fn contains_key(&self, k: usize) -> bool {
         self.assert_boundaries(k);
-        matches!(unsafe { &*self.head.add(k) }, Some(_))
+        unsafe { &*self.head.add(k) }.is_some()
     }
 
     /// Remove by key.
     ///
+    /// The [`Map`] must be initialized, because the slots next to the key
+    /// are looked at, in order to keep the bounds of the used range tight.
+    ///
     /// # Panics
     ///
     /// It may panic if you attempt to refer to they key that is outside
     /// of the boundary of this map. It will not return `None`, it will panic.
     /// However, in "release" mode it will not panic, but will lead to
-    /// undefined behavior.
+    /// undefined behavior. It may also panic in debug mode, if the [`Map`]
+    /// is not initialized.
     #[inline]
     pub fn remove(&mut self, k: usize) {
         self.assert_boundaries(k);
+        #[cfg(debug_assertions)]
+        assert!(self.initialized, "Can't remove() non-initialized Map");
         unsafe {
-            ptr::write(self.head.add(k), None);
+            raw::remove(self.head, k);
         }
+        self.narrow(k);
     }
 
-    /// Push to the rightmost position and return the key.
+    /// Push to the smallest available key and return the key.
+    ///
+    /// The key is the one [`Map::next_key`] returns, so the gaps left by
+    /// removed keys are filled first.
+    ///
+    /// # Panics
+    ///
+    /// If no more keys left.
     #[inline]
     pub fn push(&mut self, v: V) -> usize {
         let k = self.next_key();
@@ -88,6 +147,23 @@ impl<V: Clone> Map<V> {
         k
     }
 
+    /// Push right after the largest key in the map and return the key.
+    ///
+    /// Unlike [`Map::push`], it doesn't fill the gaps left by removed keys.
+    ///
+    /// # Panics
+    ///
+    /// If no more keys left after the largest one.
+    ///
+    /// It may also panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    pub fn push_back(&mut self, v: V) -> usize {
+        let k = self.last_key().map_or(0, |k| k + 1);
+        assert!(k < self.capacity(), "No more keys available left");
+        self.insert(k, v);
+        k
+    }
+
     /// Insert a single pair into the map.
     ///
     /// # Panics
@@ -100,11 +176,106 @@ impl<V: Clone> Map<V> {
     pub fn insert(&mut self, k: usize, v: V) {
         self.assert_boundaries(k);
         unsafe {
-            ptr::write(self.head.add(k), Some(v));
+            raw::insert(self.head, k, v);
         }
-        if self.max <= k {
-            self.max = k + 1;
+        self.stamp(k);
+        self.widen(k);
+    }
+
+    /// Insert a single pair into the map, expecting the key to be vacant.
+    ///
+    /// It works exactly as [`Map::insert`], but in "debug" mode also checks
+    /// that nothing is overwritten. In "release" mode the check is skipped.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in "debug" mode if the key is already occupied or
+    /// is outside of the boundary of this map.
+    #[inline]
+    pub fn insert_unique(&mut self, k: usize, v: V) {
+        debug_assert!(!self.contains_key(k), "The key {k} is already occupied");
+        self.insert(k, v);
+    }
+
+    /// Insert a single pair into the map, returning the value that was
+    /// there before, or an error if the key is outside of the boundary.
+    ///
+    /// Unlike [`Map::insert`], it never panics on a wrong key, even in
+    /// "debug" mode, so it is safe to use with untrusted keys.
+    ///
+    /// # Errors
+    ///
+    /// If the key is outside of the boundary of this map.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    pub fn checked_insert(&mut self, k: usize, v: V) -> Result<Option<V>, OutOfBoundsError> {
+        self.check_boundaries(k)?;
+        #[cfg(debug_assertions)]
+        assert!(
+            self.initialized,
+            "Can't checked_insert() non-initialized Map"
+        );
+        let old = unsafe { ptr::replace(self.head.add(k), Some(v)) };
+        self.stamp(k);
+        self.widen(k);
+        Ok(old)
+    }
+
+    /// Insert a batch of pairs into the map, checking the boundary
+    /// only once, for the largest key.
+    ///
+    /// Nothing is inserted if any of the keys is outside of the boundary.
+    ///
+    /// # Errors
+    ///
+    /// If any of the keys is outside of the boundary of this map.
+    #[inline]
+    pub fn insert_many<I: IntoIterator<Item = (usize, V)>>(
+        &mut self,
+        pairs: I,
+    ) -> Result<(), OutOfBoundsError> {
+        let pairs: Vec<(usize, V)> = pairs.into_iter().collect();
+        if let Some(top) = pairs.iter().map(|(k, _)| *k).max() {
+            self.check_boundaries(top)?;
+            for (k, v) in pairs {
+                unsafe {
+                    ptr::write(self.head.add(k), Some(v));
+                }
+                self.stamp(k);
+                self.widen(k);
+            }
+        }
+        Ok(())
+    }
+
+    /// Move the value from one key to another one, which must be empty.
+    ///
+    /// Returns `true` if the value was moved, or `false` if there is
+    /// no value at `from` or there is already a value at `to`. In the
+    /// latter case nothing changes.
+    ///
+    /// # Panics
+    ///
+    /// It may panic if you attempt to refer to they key that is outside
+    /// of the boundary of this map. It will not return `None`, it will panic.
+    /// However, in "release" mode it will not panic, but will lead to
+    /// undefined behavior.
+    #[inline]
+    pub fn relocate(&mut self, from: usize, to: usize) -> bool {
+        if !self.contains_key(from) || self.contains_key(to) {
+            return false;
+        }
+        unsafe {
+            let v = ptr::replace(self.head.add(from), None);
+            ptr::write(self.head.add(to), v);
         }
+        self.move_stamp(from, to);
+        self.widen(to);
+        self.narrow(from);
+        true
     }
 
     /// Get a reference to a single value.
@@ -137,61 +308,845 @@ impl<V: Clone> Map<V> {
         unsafe { &mut *(self.head.add(k)) }.as_mut()
     }
 
-    /// Remove all items from it, but keep the space intact for future use.
+    /// Swap the value of the key with the one provided, if the key
+    /// is present, without cloning either of them.
+    ///
+    /// Returns `true` if the values were swapped, or `false` if the key
+    /// is absent, leaving `v` untouched.
+    ///
+    /// # Panics
+    ///
+    /// It may panic if you attempt to refer to they key that is outside
+    /// of the boundary of this map. However, in "release" mode it will not
+    /// panic, but will lead to undefined behavior.
+    #[inline]
+    pub fn swap_value(&mut self, k: usize, v: &mut V) -> bool {
+        self.get_mut(k).map(|p| mem::swap(p, v)).is_some()
+    }
+
+    /// Get mutable references to a few values at once.
+    ///
+    /// # Errors
+    ///
+    /// If any of the keys is absent or outside of the boundary, or if
+    /// any two keys are the same.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    pub fn get_many_mut<const N: usize>(
+        &mut self,
+        keys: [usize; N],
+    ) -> Result<[&mut V; N], GetManyError> {
+        #[cfg(debug_assertions)]
+        assert!(self.initialized, "Can't get_many_mut() non-initialized Map");
+        for (i, k) in keys.iter().enumerate() {
+            if !matches!(self.checked_get(*k), Ok(Some(_))) {
+                return Err(GetManyError::Missing(*k));
+            }
+            if keys[..i].contains(k) {
+                return Err(GetManyError::Overlapping);
+            }
+        }
+        let head = self.head;
+        Ok(keys.map(|k| unsafe { (*head.add(k)).as_mut().unwrap_unchecked() }))
+    }
+
+    /// Get mutable references to a few values at once, without any checks.
+    ///
+    /// It is the fast version of [`Map::get_many_mut`], for hot loops
+    /// where the keys are known to be good.
+    ///
+    /// # Safety
+    ///
+    /// Every key must be inside the boundary of the map, must be occupied,
+    /// and must differ from all the other keys. Otherwise, the behavior
+    /// is undefined.
+    #[inline]
+    pub unsafe fn get_disjoint_unchecked_mut<const N: usize>(
+        &mut self,
+        keys: [usize; N],
+    ) -> [&mut V; N] {
+        let head = self.head;
+        keys.map(|k| unsafe { (*head.add(k)).as_mut().unwrap_unchecked() })
+    }
+
+    /// Get a reference to a single value, or an error if the key is outside
+    /// of the boundary.
+    ///
+    /// # Errors
+    ///
+    /// If the key is outside of the boundary of this map.
+    #[inline]
+    pub fn checked_get(&self, k: usize) -> Result<Option<&V>, OutOfBoundsError> {
+        self.check_boundaries(k)?;
+        Ok(self.get(k))
+    }
+
+    /// Get a mutable reference to a single value, or an error if the key
+    /// is outside of the boundary.
+    ///
+    /// # Errors
+    ///
+    /// If the key is outside of the boundary of this map.
+    #[inline]
+    pub fn checked_get_mut(&mut self, k: usize) -> Result<Option<&mut V>, OutOfBoundsError> {
+        self.check_boundaries(k)?;
+        Ok(self.get_mut(k))
+    }
+
+    /// Remove by key, returning the value removed, or an error if the key
+    /// is outside of the boundary.
+    ///
+    /// # Errors
+    ///
+    /// If the key is outside of the boundary of this map.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    pub fn checked_remove(&mut self, k: usize) -> Result<Option<V>, OutOfBoundsError> {
+        self.check_boundaries(k)?;
+        #[cfg(debug_assertions)]
+        assert!(
+            self.initialized,
+            "Can't checked_remove() non-initialized Map"
+        );
+        let v = unsafe { ptr::replace(self.head.add(k), None) };
+        self.narrow(k);
+        Ok(v)
+    }
+
+    /// Get a mutable reference to a single value, building and inserting
+    /// it first if the key is absent.
+    ///
+    /// The function is called only if the key is absent. If it fails,
+    /// the error is returned and the map stays unchanged.
+    ///
+    /// # Errors
+    ///
+    /// If the function returns an error.
+    ///
+    /// # Panics
+    ///
+    /// It may panic if you attempt to refer to they key that is outside
+    /// of the boundary of this map. However, in "release" mode it will not
+    /// panic, but will lead to undefined behavior.
+    #[inline]
+    pub fn get_or_try_insert_with<E, F: FnOnce() -> Result<V, E>>(
+        &mut self,
+        k: usize,
+        f: F,
+    ) -> Result<&mut V, E> {
+        self.assert_boundaries(k);
+        if !self.contains_key(k) {
+            self.insert(k, f()?);
+        }
+        Ok(unsafe { (*self.head.add(k)).as_mut().unwrap_unchecked() })
+    }
+
+    /// Apply the function to the values of the keys provided.
+    ///
+    /// The keys that are absent or outside of the boundary are skipped.
+    /// A key mentioned a few times is visited only once. The keys are
+    /// visited in ascending order.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    pub fn each_mut<F: FnMut(usize, &mut V)>(&mut self, keys: &[usize], mut f: F) {
+        #[cfg(debug_assertions)]
+        assert!(self.initialized, "Can't each_mut() non-initialized Map");
+        let mut keys = keys.to_vec();
+        keys.sort_unstable();
+        keys.dedup();
+        for k in keys {
+            if let Ok(Some(v)) = self.checked_get_mut(k) {
+                f(k, v);
+            }
+        }
+    }
+
+    /// Remove the items with the keys provided, dropping their values,
+    /// and return the number of items actually removed.
+    ///
+    /// The keys that are absent or outside of the boundary are skipped.
+    /// A key mentioned a few times is removed only once.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    pub fn bulk_remove(&mut self, keys: &[usize]) -> usize {
+        #[cfg(debug_assertions)]
+        assert!(self.initialized, "Can't bulk_remove() non-initialized Map");
+        keys.iter()
+            .filter(|k| matches!(self.checked_remove(**k), Ok(Some(_))))
+            .count()
+    }
+
+    /// Remove all items from it, dropping their values, but keep the space
+    /// intact for future use.
+    ///
+    /// The memory of the slots stays allocated and the capacity doesn't
+    /// change. To get the removed values back, for example to return
+    /// their buffers to a pool, use [`Map::clear_with`].
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
     #[inline]
     pub fn clear(&mut self) {
+        #[cfg(debug_assertions)]
+        assert!(self.initialized, "Can't clear() non-initialized Map");
+        unsafe {
+            raw::clear(self.head, self.max);
+        }
+        self.min = 0;
+        self.max = 0;
+    }
+
+    /// Remove all items from it, handing each removed value to the
+    /// function, in ascending order of keys.
+    ///
+    /// Just like [`Map::clear`], it keeps the space intact for future use.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    pub fn clear_with<F: FnMut(V)>(&mut self, mut f: F) {
+        #[cfg(debug_assertions)]
+        assert!(self.initialized, "Can't clear_with() non-initialized Map");
+        let max = self.max;
+        self.min = 0;
+        self.max = 0;
+        for k in 0..max {
+            if let Some(v) = unsafe { ptr::replace(self.head.add(k), None) } {
+                f(v);
+            }
+        }
+    }
+
+    /// Put a clone of the value into every slot, dropping the values
+    /// that were there before.
+    ///
+    /// It reuses the memory already allocated, making the map
+    /// fully occupied, just like [`Map::with_capacity_some`] does.
+    ///
+    /// If cloning panics, the slots filled before the panic stay
+    /// in the map, together with the values that were not yet replaced.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    pub fn fill(&mut self, v: V) {
+        #[cfg(debug_assertions)]
+        assert!(self.initialized, "Can't fill() non-initialized Map");
+        self.min = 0;
+        for k in 0..self.capacity() {
+            unsafe {
+                *self.head.add(k) = Some(v.clone());
+            }
+            self.stamp(k);
+            self.max = self.max.max(k + 1);
+        }
+    }
+
+    /// Drop all values and then put into every slot the value
+    /// made by the function from the key.
+    ///
+    /// If the function panics, the slots filled before the panic
+    /// stay in the map, while all others remain empty.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    pub fn reset_with<F: FnMut(usize) -> V>(&mut self, mut f: F) {
+        #[cfg(debug_assertions)]
+        assert!(self.initialized, "Can't reset_with() non-initialized Map");
+        for k in 0..self.capacity() {
+            unsafe {
+                *self.head.add(k) = None;
+            }
+        }
+        self.min = 0;
         self.max = 0;
+        for k in 0..self.capacity() {
+            let v = f(k);
+            unsafe {
+                ptr::write(self.head.add(k), Some(v));
+            }
+            self.stamp(k);
+            self.max = k + 1;
+        }
     }
 
     /// Retains only the elements specified by the predicate.
     ///
+    /// The items are visited in ascending order of keys, the same order
+    /// [`Map::keys`] goes in.
+    ///
     /// # Panics
     ///
     /// It may panic in debug mode, if the [`Map`] is not initialized.
     #[inline]
     pub fn retain<F: Fn(&usize, &V) -> bool>(&mut self, f: F) {
+        self.retain_count(f);
+    }
+
+    /// Retains only the elements specified by the predicate and returns
+    /// the number of elements removed.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    pub fn retain_count<F: Fn(&usize, &V) -> bool>(&mut self, f: F) -> usize {
         #[cfg(debug_assertions)]
         assert!(self.initialized, "Can't do retain() on non-initialized Map");
+        let mut removed = 0;
         for i in 0..self.max {
             if let Some(p) = self.get_mut(i) {
                 if !f(&i, p) {
                     unsafe {
                         ptr::write(self.head.add(i), None);
                     }
+                    removed += 1;
                 }
             }
         }
+        self.tighten();
+        removed
     }
 
-    /// Check the boundary condition.
+    /// Retains only the keys specified by the predicate, without looking
+    /// at the values.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
     #[inline]
-    #[allow(unused_variables)]
-    fn assert_boundaries(&self, k: usize) {
+    pub fn retain_keys<F: Fn(usize) -> bool>(&mut self, f: F) {
         #[cfg(debug_assertions)]
         assert!(
-            k < self.capacity(),
-            "The key {k} is over the boundary {}",
-            self.capacity()
+            self.initialized,
+            "Can't do retain_keys() on non-initialized Map"
         );
+        for i in 0..self.max {
+            if !f(i) {
+                unsafe {
+                    *self.head.add(i) = None;
+                }
+            }
+        }
+        self.tighten();
     }
-}
-
-#[test]
-fn insert_and_check_length() {
-    let mut m: Map<&str> = Map::with_capacity_none(16);
-    m.insert(0, "zero");
-    assert_eq!(1, m.len());
-    m.insert(1, "first");
-    assert_eq!(2, m.len());
-    m.insert(1, "first");
-    assert_eq!(2, m.len());
-}
-
-#[test]
-fn empty_length() {
-    let m: Map<u32> = Map::with_capacity_none(16);
-    assert_eq!(0, m.len());
-}
+
+    /// Remove all items with keys equal to or bigger than `n`, dropping
+    /// their values, but keep the capacity intact.
+    ///
+    /// The cost is proportional to the number of slots above `n` and
+    /// below the top of the used range, so it's cheaper than
+    /// [`Map::clear`] when only the tail has to go.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    pub fn truncate_keys(&mut self, n: usize) {
+        #[cfg(debug_assertions)]
+        assert!(
+            self.initialized,
+            "Can't do truncate_keys() on non-initialized Map"
+        );
+        for k in n..self.max {
+            unsafe {
+                *self.head.add(k) = None;
+            }
+        }
+        self.max = self.max.min(n);
+        self.tighten();
+    }
+
+    /// Remove the items whose keys are present in the other map,
+    /// dropping their values.
+    ///
+    /// The maps may have different capacities.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if any of the maps is not initialized.
+    #[inline]
+    pub fn remove_keys_of<U: Clone>(&mut self, other: &Map<U>) {
+        #[cfg(debug_assertions)]
+        assert!(
+            self.initialized && other.initialized,
+            "Can't do remove_keys_of() on non-initialized Map"
+        );
+        for k in other.keys() {
+            if k >= self.max {
+                break;
+            }
+            unsafe {
+                *self.head.add(k) = None;
+            }
+        }
+        self.tighten();
+    }
+
+    /// Retain only the items whose keys are present in the other map,
+    /// dropping the values of the rest.
+    ///
+    /// The maps may have different capacities.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if any of the maps is not initialized.
+    #[inline]
+    pub fn retain_keys_of<U: Clone>(&mut self, other: &Map<U>) {
+        #[cfg(debug_assertions)]
+        assert!(
+            self.initialized && other.initialized,
+            "Can't do retain_keys_of() on non-initialized Map"
+        );
+        for k in 0..self.max {
+            if !matches!(other.checked_get(k), Ok(Some(_))) {
+                unsafe {
+                    *self.head.add(k) = None;
+                }
+            }
+        }
+        self.tighten();
+    }
+
+    /// Remove the items whose values match the predicate and return
+    /// their keys, in ascending order.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    pub fn drain_keys<F: FnMut(&V) -> bool>(&mut self, mut f: F) -> Vec<usize> {
+        #[cfg(debug_assertions)]
+        assert!(
+            self.initialized,
+            "Can't do drain_keys() on non-initialized Map"
+        );
+        let mut keys = vec![];
+        for i in 0..self.max {
+            let slot = unsafe { &mut *self.head.add(i) };
+            if slot.as_ref().is_some_and(&mut f) {
+                *slot = None;
+                keys.push(i);
+            }
+        }
+        self.tighten();
+        keys
+    }
+
+    /// Pass every value to the function, by value, and keep what it
+    /// returns: `Some` replaces the value, while `None` removes the key.
+    ///
+    /// The value is taken out of its slot before the function is called,
+    /// so if the function panics, the slot stays empty.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    pub fn retain_map<F: FnMut(usize, V) -> Option<V>>(&mut self, mut f: F) {
+        #[cfg(debug_assertions)]
+        assert!(
+            self.initialized,
+            "Can't do retain_map() on non-initialized Map"
+        );
+        for k in 0..self.max {
+            if let Some(v) = unsafe { ptr::replace(self.head.add(k), None) } {
+                if let Some(v) = f(k, v) {
+                    unsafe {
+                        ptr::write(self.head.add(k), Some(v));
+                    }
+                }
+            }
+        }
+        self.tighten();
+    }
+
+    /// Retain only the elements specified by the predicate, in ascending
+    /// order of keys, until the predicate breaks the scan.
+    ///
+    /// `Continue(true)` keeps the value, `Continue(false)` removes it,
+    /// while `Break(())` stops right away, leaving the current and all
+    /// later items untouched.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    pub fn retain_while<F: FnMut(usize, &mut V) -> ControlFlow<(), bool>>(&mut self, mut f: F) {
+        #[cfg(debug_assertions)]
+        assert!(
+            self.initialized,
+            "Can't do retain_while() on non-initialized Map"
+        );
+        for k in 0..self.max {
+            let slot = unsafe { &mut *self.head.add(k) };
+            if let Some(v) = slot {
+                match f(k, v) {
+                    ControlFlow::Break(()) => break,
+                    ControlFlow::Continue(true) => {}
+                    ControlFlow::Continue(false) => *slot = None,
+                }
+            }
+        }
+        self.tighten();
+    }
+
+    /// Move all items from the other map into this one, leaving the other
+    /// one empty, but with its capacity intact.
+    ///
+    /// If a key is present in both maps, the value from the other map wins.
+    ///
+    /// # Panics
+    ///
+    /// If a key of the other map is outside of the boundary of this map.
+    /// The items moved before the panic stay in this map.
+    ///
+    /// It may also panic in debug mode, if any of the maps is not initialized.
+    #[inline]
+    pub fn append(&mut self, other: &mut Self) {
+        #[cfg(debug_assertions)]
+        assert!(
+            self.initialized && other.initialized,
+            "Can't append() non-initialized Map"
+        );
+        for k in 0..other.max {
+            if other.contains_key(k) {
+                assert!(
+                    k < self.capacity(),
+                    "The key {k} is over the boundary {}",
+                    self.capacity()
+                );
+                if let Some(v) = unsafe { ptr::replace(other.head.add(k), None) } {
+                    self.insert(k, v);
+                }
+            }
+        }
+        other.min = 0;
+        other.max = 0;
+    }
+
+    /// Move all items with keys greater or equal to `at` into a new map
+    /// of the same capacity, and return it.
+    ///
+    /// The keys stay the same in the new map, while this one keeps
+    /// only the keys below `at`.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    #[must_use]
+    pub fn split_off(&mut self, at: usize) -> Self {
+        #[cfg(debug_assertions)]
+        assert!(self.initialized, "Can't split_off() non-initialized Map");
+        let mut m = Self::with_capacity_none(self.capacity());
+        for k in at..self.max {
+            if let Some(v) = unsafe { ptr::replace(self.head.add(k), None) } {
+                m.insert(k, v);
+            }
+        }
+        #[cfg(feature = "ordered")]
+        m.copy_stamps(&self.seqs, self.seq);
+        if self.max > at {
+            self.max = at;
+        }
+        self.tighten();
+        m
+    }
+
+    /// Convert it into a map of another type, applying the function to
+    /// every value and keeping the keys.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use emap::Map;
+    /// let mut m: Map<&str> = Map::with_capacity_none(16);
+    /// m.insert(3, "42");
+    /// let n: Map<i32> = m.map_values(|v| v.parse().unwrap());
+    /// assert_eq!(42, n[3]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    #[must_use]
+    pub fn map_values<U: Clone, F: FnMut(V) -> U>(self, mut f: F) -> Map<U> {
+        #[cfg(debug_assertions)]
+        assert!(self.initialized, "Can't map_values() non-initialized Map");
+        let mut m = Map::with_capacity_none(self.capacity());
+        for k in 0..self.max {
+            if let Some(v) = unsafe { ptr::replace(self.head.add(k), None) } {
+                m.insert(k, f(v));
+            }
+        }
+        #[cfg(feature = "ordered")]
+        m.copy_stamps(&self.seqs, self.seq);
+        m
+    }
+
+    /// Make a map of the same capacity with the same keys occupied,
+    /// but with default values.
+    ///
+    /// It's handy for a parallel map, keyed the same way as this one.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    #[must_use]
+    pub fn skeleton<U: Clone + Default>(&self) -> Map<U> {
+        #[cfg(debug_assertions)]
+        assert!(self.initialized, "Can't skeleton() non-initialized Map");
+        let mut m = Map::with_capacity_none(self.capacity());
+        for k in self.keys() {
+            m.insert(k, U::default());
+        }
+        m
+    }
+
+    /// Move all values down to the smallest keys, keeping their order,
+    /// so that the keys `0..len` are occupied.
+    ///
+    /// Returns the pairs of old and new keys, for every value that moved.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    pub fn compact_keys(&mut self) -> Vec<(usize, usize)> {
+        #[cfg(debug_assertions)]
+        assert!(self.initialized, "Can't compact_keys() non-initialized Map");
+        let mut moved = vec![];
+        let mut next = 0;
+        for k in 0..self.max {
+            if self.contains_key(k) {
+                if k != next {
+                    unsafe {
+                        let v = ptr::replace(self.head.add(k), None);
+                        ptr::write(self.head.add(next), v);
+                    }
+                    self.move_stamp(k, next);
+                    moved.push((k, next));
+                }
+                next += 1;
+            }
+        }
+        self.min = 0;
+        self.max = next;
+        moved
+    }
+
+    /// Check the internal invariants of the map, which is helpful
+    /// for fuzzing and debugging of unsafe code around it.
+    ///
+    /// The top of the used range of keys must not be above the capacity,
+    /// and all the slots above it and below its bottom must be empty.
+    ///
+    /// # Errors
+    ///
+    /// With the description of the first broken invariant.
+    #[inline]
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if self.max > self.capacity() {
+            return Err("The top of the used range is over the capacity");
+        }
+        #[cfg(debug_assertions)]
+        if !self.initialized {
+            return Err("The Map is not initialized");
+        }
+        if (self.max..self.capacity()).any(|k| unsafe { &*self.head.add(k) }.is_some()) {
+            return Err("There is a value above the top of the used range");
+        }
+        if (0..self.min.min(self.max)).any(|k| unsafe { &*self.head.add(k) }.is_some()) {
+            return Err("There is a value below the bottom of the used range");
+        }
+        Ok(())
+    }
+
+    /// Apply a single operation, which is how fuzzers and property tests
+    /// drive the map through random sequences of operations.
+    ///
+    /// # Panics
+    ///
+    /// Just like the method the operation stands for.
+    #[doc(hidden)]
+    #[inline]
+    pub fn apply_op(&mut self, op: Op<V>) {
+        match op {
+            Op::Insert(k, v) => self.insert(k, v),
+            Op::Remove(k) => self.remove(k),
+            Op::Push(v) => {
+                self.push(v);
+            }
+            Op::Clear => self.clear(),
+        }
+    }
+
+    /// Check the boundary condition, without panicking.
+    #[inline]
+    pub(crate) const fn check_boundaries(&self, k: usize) -> Result<(), OutOfBoundsError> {
+        if k < self.capacity() {
+            Ok(())
+        } else {
+            Err(OutOfBoundsError {
+                key: k,
+                capacity: self.capacity(),
+            })
+        }
+    }
+
+    /// Extend the used range of keys to include the key just occupied.
+    ///
+    /// All slots below `min` and at or above `max` are always empty, so
+    /// `min >= max` means that the map is empty.
+    #[inline]
+    pub(crate) const fn widen(&mut self, k: usize) {
+        if self.min >= self.max {
+            self.min = k;
+            self.max = k + 1;
+        } else if k < self.min {
+            self.min = k;
+        } else if self.max <= k {
+            self.max = k + 1;
+        }
+    }
+
+    /// Shrink the used range of keys, if the key just emptied was
+    /// at one of its ends, skipping all empty slots next to it.
+    #[inline]
+    pub(crate) fn narrow(&mut self, k: usize) {
+        if k == self.min {
+            while self.min < self.max && unsafe { &*self.head.add(self.min) }.is_none() {
+                self.min += 1;
+            }
+        }
+        if k + 1 == self.max {
+            while self.max > self.min && unsafe { &*self.head.add(self.max - 1) }.is_none() {
+                self.max -= 1;
+            }
+        }
+    }
+
+    /// Shrink the used range of keys from both ends, skipping all
+    /// empty slots, after many keys were emptied at once.
+    #[inline]
+    pub(crate) fn tighten(&mut self) {
+        self.narrow(self.min);
+        if self.max > 0 {
+            self.narrow(self.max - 1);
+        }
+    }
+
+    /// Give the key the next insertion sequence number, if the `ordered`
+    /// feature is on.
+    #[inline]
+    #[allow(
+        unused_variables,
+        clippy::missing_const_for_fn,
+        clippy::unused_self,
+        clippy::needless_pass_by_ref_mut
+    )]
+    pub(crate) fn stamp(&mut self, k: usize) {
+        #[cfg(feature = "ordered")]
+        {
+            self.seq += 1;
+            self.seqs[k] = self.seq;
+        }
+    }
+
+    /// Move the insertion sequence number along with the value, if
+    /// the `ordered` feature is on.
+    #[inline]
+    #[allow(
+        unused_variables,
+        clippy::missing_const_for_fn,
+        clippy::unused_self,
+        clippy::needless_pass_by_ref_mut
+    )]
+    fn move_stamp(&mut self, from: usize, to: usize) {
+        #[cfg(feature = "ordered")]
+        {
+            self.seqs[to] = self.seqs[from];
+        }
+    }
+
+    /// Check the boundary condition.
+    #[inline]
+    #[allow(unused_variables, clippy::unused_self, clippy::missing_const_for_fn)]
+    fn assert_boundaries(&self, k: usize) {
+        #[cfg(debug_assertions)]
+        assert!(
+            k < self.capacity(),
+            "The key {k} is over the boundary {}",
+            self.capacity()
+        );
+    }
+}
+
+impl<V: Clone + Default> Map<V> {
+    /// Get a mutable reference to a single value, inserting the default
+    /// one first if the key is absent.
+    ///
+    /// For example, here is how values can be grouped by keys:
+    ///
+    /// ```
+    /// use emap::Map;
+    /// let mut m: Map<Vec<i32>> = Map::with_capacity_none(16);
+    /// m.get_or_insert_default(3).push(42);
+    /// m.get_or_insert_default(3).push(7);
+    /// assert_eq!(vec![42, 7], m[3]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// It may panic if you attempt to refer to they key that is outside
+    /// of the boundary of this map. It will not return `None`, it will panic.
+    /// However, in "release" mode it will not panic, but will lead to
+    /// undefined behavior.
+    #[inline]
+    pub fn get_or_insert_default(&mut self, k: usize) -> &mut V {
+        if !self.contains_key(k) {
+            self.insert(k, V::default());
+        }
+        unsafe { (*self.head.add(k)).as_mut().unwrap_unchecked() }
+    }
+}
+
+#[test]
+fn insert_and_check_length() {
+    let mut m: Map<&str> = Map::with_capacity_none(16);
+    m.insert(0, "zero");
+    assert_eq!(1, m.len());
+    m.insert(1, "first");
+    assert_eq!(2, m.len());
+    m.insert(1, "first");
+    assert_eq!(2, m.len());
+}
+
+#[test]
+fn empty_length() {
+    let m: Map<u32> = Map::with_capacity_none(16);
+    assert_eq!(0, m.len());
+}
 
 #[test]
 fn is_empty_check() {
@@ -201,6 +1156,32 @@ fn is_empty_check() {
     assert!(!m.is_empty());
 }
 
+#[test]
+fn calculates_load_factor() {
+    let mut m: Map<u64> = Map::with_capacity_none(4);
+    assert!(m.load_factor().abs() < f64::EPSILON);
+    m.insert(0, 42);
+    m.insert(3, 42);
+    assert!((m.load_factor() - 0.5).abs() < f64::EPSILON);
+    m.fill(42);
+    assert!((m.load_factor() - 1.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn calculates_load_factor_of_zero_capacity() {
+    let m: Map<u64> = Map::with_capacity_none(0);
+    assert!(m.load_factor().abs() < f64::EPSILON);
+}
+
+#[test]
+fn calculates_occupied_bytes() {
+    let mut m: Map<u64> = Map::with_capacity_none(4);
+    assert_eq!(0, m.occupied_bytes());
+    m.insert(1, 42);
+    m.insert(2, 42);
+    assert_eq!(32, m.occupied_bytes());
+}
+
 #[test]
 fn insert_and_gets() {
     let mut m: Map<&str> = Map::with_capacity_none(16);
@@ -255,10 +1236,751 @@ fn removes_simple_pair() {
     assert!(m.get(0).is_none());
 }
 
-#[cfg(test)]
-#[derive(Clone, Copy)]
-struct Foo {
-    v: [u32; 3],
+#[test]
+fn appends_disjoint_maps() {
+    let mut m: Map<&str> = Map::with_capacity_none(16);
+    m.insert(0, "zero");
+    let mut other: Map<&str> = Map::with_capacity_none(8);
+    other.insert(3, "three");
+    other.insert(5, "five");
+    m.append(&mut other);
+    assert_eq!(3, m.len());
+    assert_eq!("five", *m.get(5).unwrap());
+    assert!(other.is_empty());
+    assert_eq!(8, other.capacity());
+}
+
+#[test]
+fn appends_overlapping_maps() {
+    let mut m: Map<&str> = Map::with_capacity_none(16);
+    m.insert(1, "one");
+    m.insert(2, "two");
+    let mut other: Map<&str> = Map::with_capacity_none(16);
+    other.insert(2, "deux");
+    m.append(&mut other);
+    assert_eq!(2, m.len());
+    assert_eq!("deux", *m.get(2).unwrap());
+    assert!(!other.contains_key(2));
+}
+
+#[test]
+#[should_panic(expected = "The key 10 is over the boundary 4")]
+fn appends_beyond_boundary() {
+    let mut m: Map<&str> = Map::with_capacity_none(4);
+    let mut other: Map<&str> = Map::with_capacity_none(16);
+    other.insert(10, "ten");
+    m.append(&mut other);
+}
+
+#[test]
+fn splits_off_sparse_map() {
+    let mut m: Map<&str> = Map::with_capacity_none(16);
+    m.insert(1, "one");
+    m.insert(4, "four");
+    m.insert(9, "nine");
+    m.insert(12, "twelve");
+    let upper = m.split_off(5);
+    assert_eq!(16, upper.capacity());
+    assert_eq!(4, m.len() + upper.len());
+    assert_eq!(vec![1, 4], m.keys().collect::<Vec<usize>>());
+    assert_eq!(vec![9, 12], upper.keys().collect::<Vec<usize>>());
+    assert_eq!("twelve", *upper.get(12).unwrap());
+}
+
+#[test]
+fn splits_off_beyond_last_key() {
+    let mut m: Map<&str> = Map::with_capacity_none(16);
+    m.insert(1, "one");
+    let upper = m.split_off(8);
+    assert!(upper.is_empty());
+    assert_eq!(1, m.len());
+    assert_eq!("one", *m.get(1).unwrap());
+}
+
+#[test]
+fn maps_values_to_another_type() {
+    let mut m: Map<&str> = Map::with_capacity_none(16);
+    m.insert(0, "7");
+    m.insert(2, "-12");
+    m.insert(5, "100");
+    let n: Map<i32> = m.map_values(|v| v.parse().unwrap());
+    assert_eq!(16, n.capacity());
+    assert_eq!(3, n.len());
+    assert_eq!(-12, n[2]);
+    assert_eq!(100, n[5]);
+    assert_eq!(1, n.next_key());
+}
+
+#[test]
+fn counts_retained_removals() {
+    let mut m: Map<u32> = Map::with_capacity_none(16);
+    for k in 0..10 {
+        m.insert(k, 42);
+    }
+    m.remove(3);
+    let before = m.len();
+    let removed = m.retain_count(|k, _| k % 2 == 0);
+    assert_eq!(4, removed);
+    assert_eq!(before - removed, m.len());
+}
+
+#[test]
+fn retains_in_ascending_order() {
+    let mut m: Map<u32> = Map::with_capacity_none(16);
+    for k in [9, 2, 14, 0, 5] {
+        m.insert(k, 42);
+    }
+    let keys: Vec<usize> = m.keys().collect();
+    let visited = RefCell::new(vec![]);
+    m.retain(|k, _| {
+        visited.borrow_mut().push(*k);
+        k % 2 == 0
+    });
+    assert_eq!(vec![0, 2, 5, 9, 14], visited.into_inner());
+    assert_eq!(vec![0, 2, 5, 9, 14], keys);
+}
+
+#[test]
+fn counts_nothing_when_all_retained() {
+    let mut m: Map<u32> = Map::with_capacity_none(16);
+    m.insert(1, 42);
+    m.insert(7, 42);
+    assert_eq!(0, m.retain_count(|_, v| *v == 42));
+    assert_eq!(2, m.len());
+}
+
+#[test]
+fn checked_insert_into_empty_slot() {
+    let mut m: Map<&str> = Map::with_capacity_none(16);
+    assert_eq!(Ok(None), m.checked_insert(3, "three"));
+    assert_eq!("three", *m.get(3).unwrap());
+}
+
+#[test]
+fn checked_insert_returns_old_value() {
+    let mut m: Map<&str> = Map::with_capacity_none(16);
+    m.insert(3, "three");
+    assert_eq!(Ok(Some("three")), m.checked_insert(3, "trois"));
+    assert_eq!("trois", *m.get(3).unwrap());
+    assert_eq!(1, m.len());
+}
+
+#[test]
+fn checked_insert_ignores_cleared_value() {
+    let mut m: Map<&str> = Map::with_capacity_none(16);
+    m.insert(3, "three");
+    m.clear();
+    assert_eq!(Ok(None), m.checked_insert(3, "trois"));
+}
+
+#[test]
+fn checked_insert_out_of_boundary() {
+    let mut m: Map<&str> = Map::with_capacity_none(4);
+    assert_eq!(
+        Err(OutOfBoundsError {
+            key: 4,
+            capacity: 4
+        }),
+        m.checked_insert(4, "four")
+    );
+    assert!(m.is_empty());
+}
+
+#[test]
+fn checked_get_matches_get() {
+    let mut m: Map<&str> = Map::with_capacity_none(16);
+    m.insert(2, "two");
+    assert_eq!(Ok(m.get(2)), m.checked_get(2));
+    assert_eq!(Ok(None), m.checked_get(3));
+    *m.checked_get_mut(2).unwrap().unwrap() = "deux";
+    assert_eq!("deux", *m.get(2).unwrap());
+}
+
+#[test]
+fn checked_remove_returns_value() {
+    let mut m: Map<&str> = Map::with_capacity_none(16);
+    m.insert(2, "two");
+    assert_eq!(Ok(Some("two")), m.checked_remove(2));
+    assert_eq!(Ok(None), m.checked_remove(2));
+    assert!(!m.contains_key(2));
+}
+
+#[test]
+fn checked_access_out_of_boundary() {
+    let mut m: Map<&str> = Map::with_capacity_none(4);
+    let e = OutOfBoundsError {
+        key: 9,
+        capacity: 4,
+    };
+    assert_eq!(Err(e), m.checked_get(9));
+    assert_eq!(Err(e), m.checked_get_mut(9));
+    assert_eq!(Err(e), m.checked_remove(9));
+}
+
+#[test]
+fn inserts_many_pairs() {
+    let mut m: Map<&str> = Map::with_capacity_none(16);
+    m.insert(0, "zero");
+    assert!(m
+        .insert_many([(3, "three"), (1, "one"), (15, "fifteen")])
+        .is_ok());
+    assert_eq!(4, m.len());
+    assert_eq!("fifteen", *m.get(15).unwrap());
+    assert_eq!(2, m.next_key());
+}
+
+#[test]
+fn inserts_nothing_beyond_boundary() {
+    let mut m: Map<&str> = Map::with_capacity_none(4);
+    m.insert(0, "zero");
+    assert_eq!(
+        Err(OutOfBoundsError {
+            key: 9,
+            capacity: 4
+        }),
+        m.insert_many([(1, "one"), (9, "nine"), (2, "two")])
+    );
+    assert_eq!(1, m.len());
+    assert!(!m.contains_key(1));
+}
+
+#[test]
+fn fills_partially_occupied_map() {
+    let mut m: Map<u32> = Map::with_capacity_none(8);
+    m.insert(2, 1);
+    m.insert(5, 1);
+    m.fill(42);
+    assert_eq!(m.capacity(), m.len());
+    assert!((0..8).all(|k| m[k] == 42));
+}
+
+#[test]
+fn keeps_bounds_when_fill_panics() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+    use std::rc::Rc;
+    struct Bomb(Rc<Cell<usize>>);
+    impl Clone for Bomb {
+        fn clone(&self) -> Self {
+            self.0.set(self.0.get() + 1);
+            assert!(self.0.get() < 3, "boom");
+            Self(Rc::clone(&self.0))
+        }
+    }
+    let clones = Rc::new(Cell::new(0));
+    let mut m: Map<Bomb> = Map::with_capacity_none(8);
+    m.insert(5, Bomb(Rc::clone(&clones)));
+    let r = catch_unwind(AssertUnwindSafe(|| m.fill(Bomb(Rc::clone(&clones)))));
+    assert!(r.is_err());
+    assert_eq!(vec![0, 1, 5], m.keys().collect::<Vec<_>>());
+    assert_eq!(Some(0), m.first_key());
+    assert_eq!(Some(5), m.last_key());
+}
+
+#[test]
+fn fill_drops_previous_values() {
+    use std::rc::Rc;
+    let old = Rc::new(0);
+    let new = Rc::new(1);
+    let mut m: Map<Rc<i32>> = Map::with_capacity_none(4);
+    m.insert(1, Rc::clone(&old));
+    m.fill(Rc::clone(&new));
+    assert_eq!(1, Rc::strong_count(&old));
+    assert_eq!(5, Rc::strong_count(&new));
+}
+
+#[test]
+fn resets_with_function() {
+    let calls = Cell::new(0);
+    let mut m: Map<usize> = Map::with_capacity_none(8);
+    m.insert(3, 42);
+    m.reset_with(|k| {
+        calls.set(calls.get() + 1);
+        k * 10
+    });
+    assert_eq!(8, calls.get());
+    assert_eq!(8, m.len());
+    assert_eq!(30, m[3]);
+    assert_eq!(70, m[7]);
+}
+
+#[test]
+fn resets_partially_on_panic() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+    use std::rc::Rc;
+    let v = Rc::new(());
+    let mut m: Map<Rc<()>> = Map::with_capacity_none(8);
+    m.insert(6, Rc::clone(&v));
+    let r = catch_unwind(AssertUnwindSafe(|| {
+        m.reset_with(|k| {
+            assert!(k < 3, "enough");
+            Rc::clone(&v)
+        });
+    }));
+    assert!(r.is_err());
+    assert_eq!(3, m.len());
+    assert!(!m.contains_key(6));
+    assert_eq!(4, Rc::strong_count(&v));
+}
+
+#[test]
+fn relocates_value() {
+    let mut m: Map<&str> = Map::with_capacity_none(16);
+    m.insert(9, "nine");
+    assert!(m.relocate(9, 2));
+    assert!(!m.contains_key(9));
+    assert_eq!("nine", m[2]);
+    assert_eq!(1, m.len());
+    assert!(m.relocate(2, 12));
+    assert_eq!(vec![12], m.keys().collect::<Vec<usize>>());
+}
+
+#[test]
+fn refuses_to_relocate_into_occupied() {
+    let mut m: Map<&str> = Map::with_capacity_none(16);
+    m.insert(1, "one");
+    m.insert(2, "two");
+    assert!(!m.relocate(1, 2));
+    assert_eq!("one", m[1]);
+    assert_eq!("two", m[2]);
+    assert_eq!(2, m.len());
+}
+
+#[test]
+fn refuses_to_relocate_from_empty() {
+    let mut m: Map<&str> = Map::with_capacity_none(16);
+    m.insert(2, "two");
+    assert!(!m.relocate(1, 3));
+    assert!(!m.contains_key(3));
+    assert_eq!(1, m.len());
+}
+
+#[test]
+fn compacts_keys() {
+    let mut m: Map<&str> = Map::with_capacity_none(16);
+    m.insert(0, "zero");
+    m.insert(3, "three");
+    m.insert(4, "four");
+    m.insert(9, "nine");
+    let moved = m.compact_keys();
+    assert_eq!(vec![(3, 1), (4, 2), (9, 3)], moved);
+    assert_eq!(vec![0, 1, 2, 3], m.keys().collect::<Vec<usize>>());
+    assert_eq!("nine", m[3]);
+    assert_eq!(4, m.next_key());
+}
+
+#[test]
+fn compacts_dense_keys() {
+    let mut m: Map<&str> = Map::with_capacity_none(16);
+    m.insert(0, "zero");
+    m.insert(1, "one");
+    assert!(m.compact_keys().is_empty());
+    assert_eq!(2, m.len());
+}
+
+#[test]
+fn updates_subset_of_keys() {
+    let mut m: Map<u32> = Map::with_capacity_none(8);
+    for k in 0..6 {
+        m.insert(k, 1);
+    }
+    m.remove(4);
+    m.each_mut(&[5, 1, 4, 100, 1, 3], |_, v| *v += 10);
+    let values: Vec<u32> = m.values().copied().collect();
+    assert_eq!(vec![1, 11, 1, 11, 11], values);
+    assert!(!m.contains_key(4));
+}
+
+#[test]
+fn clears_with_each_value() {
+    let mut m: Map<Vec<u8>> = Map::with_capacity_none(8);
+    m.insert(1, vec![1]);
+    m.insert(3, vec![3, 3]);
+    m.insert(6, vec![6]);
+    m.remove(6);
+    let mut pool = vec![];
+    m.clear_with(|v| pool.push(v));
+    assert_eq!(vec![vec![1], vec![3, 3]], pool);
+    assert!(m.is_empty());
+    assert_eq!(0, m.next_key());
+    assert_eq!(8, m.capacity());
+}
+
+#[test]
+fn gets_many_mut() {
+    let mut m: Map<u32> = Map::with_capacity_none(8);
+    m.insert(1, 10);
+    m.insert(4, 40);
+    m.insert(6, 60);
+    let [a, b] = m.get_many_mut([6, 1]).unwrap();
+    mem::swap(a, b);
+    assert_eq!(60, m[1]);
+    assert_eq!(10, m[6]);
+}
+
+#[test]
+fn gets_disjoint_unchecked_mut() {
+    let mut m: Map<u32> = Map::with_capacity_none(8);
+    m.insert(2, 20);
+    m.insert(7, 70);
+    let [a, b] = unsafe { m.get_disjoint_unchecked_mut([2, 7]) };
+    mem::swap(a, b);
+    assert_eq!(vec![70, 20], m.values().copied().collect::<Vec<_>>());
+}
+
+#[test]
+fn fails_get_many_mut_on_missing_key() {
+    let mut m: Map<u32> = Map::with_capacity_none(8);
+    m.insert(1, 10);
+    assert_eq!(Err(GetManyError::Missing(2)), m.get_many_mut([1, 2]));
+    assert_eq!(Err(GetManyError::Missing(99)), m.get_many_mut([99]));
+}
+
+#[test]
+fn fails_get_many_mut_on_overlapping_keys() {
+    let mut m: Map<u32> = Map::with_capacity_none(8);
+    m.insert(1, 10);
+    m.insert(2, 20);
+    assert_eq!(Err(GetManyError::Overlapping), m.get_many_mut([1, 2, 1]));
+}
+
+#[test]
+fn retains_odd_keys() {
+    let mut m: Map<u32> = Map::with_capacity_none(16);
+    for k in [0, 3, 4, 7, 10, 13] {
+        m.insert(k, 42);
+    }
+    m.retain_keys(|k| k % 2 == 1);
+    assert_eq!(vec![3, 7, 13], m.keys().collect::<Vec<_>>());
+}
+
+#[test]
+fn drops_values_on_retain_keys() {
+    use std::rc::Rc;
+    let v = Rc::new(());
+    let mut m: Map<Rc<()>> = Map::with_capacity_none(8);
+    for k in 0..4 {
+        m.insert(k, Rc::clone(&v));
+    }
+    m.retain_keys(|k| k % 2 == 0);
+    assert_eq!(vec![0, 2], m.keys().collect::<Vec<_>>());
+    assert_eq!(3, Rc::strong_count(&v));
+}
+
+#[test]
+fn drains_keys_of_even_values() {
+    let mut m: Map<u32> = Map::with_capacity_none(8);
+    for (k, v) in [(0, 1), (2, 4), (3, 6), (5, 7), (7, 8)] {
+        m.insert(k, v);
+    }
+    assert_eq!(vec![2, 3, 7], m.drain_keys(|v| v % 2 == 0));
+    assert_eq!(vec![(0, &1), (5, &7)], m.iter().collect::<Vec<_>>());
+    assert!(m.drain_keys(|v| v % 2 == 0).is_empty());
+}
+
+#[test]
+fn validates_healthy_map() {
+    let mut m: Map<u32> = Map::with_capacity_none(8);
+    assert_eq!(Ok(()), m.validate());
+    m.insert(3, 42);
+    m.insert(7, 42);
+    m.remove(7);
+    m.compact_keys();
+    assert_eq!(Ok(()), m.validate());
+    m.clear();
+    assert_eq!(Ok(()), m.validate());
+}
+
+#[test]
+fn finds_value_above_used_range() {
+    let mut m: Map<u32> = Map::with_capacity_none(8);
+    m.insert(5, 42);
+    m.max = 2;
+    assert_eq!(
+        Err("There is a value above the top of the used range"),
+        m.validate()
+    );
+}
+
+#[test]
+fn finds_value_below_used_range() {
+    let mut m: Map<u32> = Map::with_capacity_none(8);
+    m.insert(1, 42);
+    m.insert(5, 42);
+    m.min = 3;
+    assert_eq!(
+        Err("There is a value below the bottom of the used range"),
+        m.validate()
+    );
+}
+
+#[test]
+fn finds_used_range_over_capacity() {
+    let mut m: Map<u32> = Map::with_capacity_none(8);
+    m.max = 9;
+    assert_eq!(
+        Err("The top of the used range is over the capacity"),
+        m.validate()
+    );
+    m.max = 0;
+}
+
+#[test]
+fn makes_skeleton() {
+    let mut m: Map<&str> = Map::with_capacity_none(8);
+    m.insert(0, "a");
+    m.insert(3, "b");
+    m.insert(6, "c");
+    m.remove(0);
+    let s: Map<f64> = m.skeleton();
+    assert_eq!(m.capacity(), s.capacity());
+    assert_eq!(m.keys().collect::<Vec<_>>(), s.keys().collect::<Vec<_>>());
+    assert_eq!(m.next_key(), s.next_key());
+    assert!(s.values().all(|v| *v == 0.0));
+}
+
+#[test]
+fn retains_and_maps_values() {
+    let mut m: Map<String> = Map::with_capacity_none(8);
+    for k in 0..5 {
+        m.insert(k, k.to_string());
+    }
+    m.retain_map(|k, v| if k % 2 == 0 { Some(v + "!") } else { None });
+    assert_eq!(3, m.len());
+    assert_eq!(
+        vec!["0!", "2!", "4!"],
+        m.values().map(String::as_str).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn leaves_slot_empty_when_retain_map_panics() {
+    let mut m: Map<String> = Map::with_capacity_none(4);
+    m.insert(0, "a".to_string());
+    m.insert(1, "b".to_string());
+    m.insert(2, "c".to_string());
+    let r = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        m.retain_map(|k, v| {
+            assert_ne!(1, k, "boom");
+            Some(v)
+        });
+    }));
+    assert!(r.is_err());
+    assert_eq!(vec![0, 2], m.keys().collect::<Vec<_>>());
+    assert_eq!("c", m[2]);
+}
+
+#[test]
+fn retains_until_break() {
+    let mut m: Map<u32> = Map::with_capacity_none(8);
+    for k in 0..6 {
+        m.insert(k, u32::try_from(k).unwrap());
+    }
+    let mut seen = vec![];
+    m.retain_while(|k, v| {
+        seen.push(k);
+        if k == 3 {
+            return ControlFlow::Break(());
+        }
+        *v += 10;
+        ControlFlow::Continue(k != 1)
+    });
+    assert_eq!(vec![0, 1, 2, 3], seen);
+    assert_eq!(vec![0, 2, 3, 4, 5], m.keys().collect::<Vec<_>>());
+    assert_eq!(
+        vec![10, 12, 3, 4, 5],
+        m.values().copied().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn retains_everything_without_break() {
+    let mut m: Map<u32> = Map::with_capacity_none(8);
+    m.insert(1, 1);
+    m.insert(5, 5);
+    m.retain_while(|_, v| ControlFlow::Continue(*v > 2));
+    assert_eq!(vec![5], m.keys().collect::<Vec<_>>());
+}
+
+#[test]
+fn gets_without_calling_the_builder() {
+    let mut m: Map<u32> = Map::with_capacity_none(8);
+    m.insert(3, 42);
+    let v = m.get_or_try_insert_with::<(), _>(3, || panic!("Must not be called"));
+    assert_eq!(Ok(&mut 42), v);
+}
+
+#[test]
+fn inserts_what_the_builder_returns() {
+    let mut m: Map<u32> = Map::with_capacity_none(8);
+    *m.get_or_try_insert_with::<(), _>(5, || Ok(7)).unwrap() += 1;
+    assert_eq!(8, m[5]);
+    assert_eq!(1, m.len());
+}
+
+#[test]
+fn keeps_map_intact_when_builder_fails() {
+    let mut m: Map<u32> = Map::with_capacity_none(8);
+    m.insert(1, 1);
+    assert_eq!(Err("oops"), m.get_or_try_insert_with(5, || Err("oops")));
+    assert_eq!(vec![1], m.keys().collect::<Vec<_>>());
+    assert!(!m.contains_key(5));
+}
+
+#[test]
+fn inserts_unique_like_insert() {
+    let mut a: Map<u32> = Map::with_capacity_none(8);
+    let mut b: Map<u32> = Map::with_capacity_none(8);
+    for k in [5, 1, 3] {
+        a.insert(k, 42);
+        b.insert_unique(k, 42);
+    }
+    assert!(a.iter().eq(b.iter()));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "The key 3 is already occupied")]
+fn panics_on_non_unique_insert() {
+    let mut m: Map<u32> = Map::with_capacity_none(8);
+    m.insert_unique(3, 1);
+    m.insert_unique(3, 2);
+}
+
+#[test]
+fn removes_keys_of_other_map() {
+    use std::rc::Rc;
+    let v = Rc::new(());
+    let mut m: Map<Rc<()>> = Map::with_capacity_none(8);
+    for k in [1, 2, 5, 7] {
+        m.insert(k, Rc::clone(&v));
+    }
+    let mut other: Map<&str> = Map::with_capacity_none(16);
+    for k in [0, 2, 7, 12] {
+        other.insert(k, "x");
+    }
+    m.remove_keys_of(&other);
+    assert_eq!(vec![1, 5], m.keys().collect::<Vec<_>>());
+    assert_eq!(3, Rc::strong_count(&v));
+}
+
+#[test]
+fn retains_keys_of_other_map() {
+    let mut m: Map<u32> = Map::with_capacity_none(16);
+    for k in [1, 2, 5, 12] {
+        m.insert(k, 42);
+    }
+    let mut other: Map<&str> = Map::with_capacity_none(8);
+    for k in [0, 2, 5, 7] {
+        other.insert(k, "x");
+    }
+    m.retain_keys_of(&other);
+    assert_eq!(vec![2, 5], m.keys().collect::<Vec<_>>());
+    let same = m.clone();
+    m.retain_keys_of(&same);
+    assert_eq!(vec![2, 5], m.keys().collect::<Vec<_>>());
+    m.retain_keys_of(&Map::<u8>::with_capacity_none(4));
+    assert!(m.is_empty());
+}
+
+#[test]
+fn swaps_values_in_place() {
+    let mut m: Map<Vec<u8>> = Map::with_capacity_none(8);
+    m.insert(2, vec![1, 2, 3]);
+    let mut buf = vec![9];
+    assert!(m.swap_value(2, &mut buf));
+    assert_eq!(vec![1, 2, 3], buf);
+    assert_eq!(vec![9], m[2]);
+    assert!(!m.swap_value(3, &mut buf));
+    assert_eq!(vec![1, 2, 3], buf);
+    assert!(!m.contains_key(3));
+}
+
+#[test]
+fn truncates_keys() {
+    use std::rc::Rc;
+    let v = Rc::new(());
+    let mut m: Map<Rc<()>> = Map::with_capacity_none(16);
+    for k in 0..10 {
+        m.insert(k, Rc::clone(&v));
+    }
+    m.truncate_keys(5);
+    assert_eq!((0..5).collect::<Vec<_>>(), m.keys().collect::<Vec<_>>());
+    assert_eq!(16, m.capacity());
+    assert_eq!(6, Rc::strong_count(&v));
+    assert_eq!(5, m.next_key());
+    m.truncate_keys(12);
+    assert_eq!(5, m.len());
+}
+
+#[test]
+fn measures_fragmentation() {
+    let mut m: Map<u32> = Map::with_capacity_none(16);
+    assert!(m.fragmentation().abs() < f64::EPSILON);
+    for k in 3..7 {
+        m.insert(k, 42);
+    }
+    assert!(m.fragmentation().abs() < f64::EPSILON);
+    m.insert(12, 42);
+    assert!((m.fragmentation() - 0.5).abs() < f64::EPSILON);
+    m.compact_keys();
+    assert!(m.fragmentation().abs() < f64::EPSILON);
+}
+
+#[test]
+fn removes_in_bulk() {
+    let mut m: Map<String> = Map::with_capacity_none(8);
+    for k in [1, 3, 5, 7] {
+        m.insert(k, k.to_string());
+    }
+    assert_eq!(2, m.bulk_remove(&[3, 2, 7, 3, 100, usize::MAX]));
+    assert_eq!(vec![1, 5], m.keys().collect::<Vec<_>>());
+    assert_eq!(0, m.bulk_remove(&[]));
+}
+
+#[test]
+fn groups_into_vectors() {
+    let mut m: Map<Vec<i32>> = Map::with_capacity_none(16);
+    for (k, v) in [(1, 10), (3, 30), (1, 11), (3, 31), (1, 12)] {
+        m.get_or_insert_default(k).push(v);
+    }
+    assert_eq!(2, m.len());
+    assert_eq!(vec![10, 11, 12], m[1]);
+    assert_eq!(vec![30, 31], m[3]);
+}
+
+#[cfg(test)]
+use std::cell::{Cell, RefCell};
+
+#[cfg(test)]
+thread_local! {
+    static DEFAULTS: Cell<usize> = const { Cell::new(0) };
+}
+
+#[cfg(test)]
+#[derive(Clone)]
+struct Counted;
+
+#[cfg(test)]
+impl Default for Counted {
+    fn default() -> Self {
+        DEFAULTS.with(|c| c.set(c.get() + 1));
+        Self
+    }
+}
+
+#[test]
+fn makes_default_only_when_vacant() {
+    let mut m: Map<Counted> = Map::with_capacity_none(4);
+    m.get_or_insert_default(2);
+    m.get_or_insert_default(2);
+    m.get_or_insert_default(2);
+    assert_eq!(1, DEFAULTS.with(Cell::get));
+}
+
+#[cfg(test)]
+#[derive(Clone, Copy)]
+struct Foo {
+    v: [u32; 3],
 }
 
 #[test]
@@ -275,6 +1997,18 @@ fn large_map_in_heap() {
     assert_eq!(0, m.len());
 }
 
+#[test]
+fn keeps_huge_values_off_the_stack() {
+    assert_eq!(
+        mem::size_of::<Map<u8>>(),
+        mem::size_of::<Map<[u64; 1 << 11]>>()
+    );
+    let mut m: Box<Map<[u64; 1 << 11]>> = Box::new(Map::with_capacity_none(4));
+    assert!(m.is_empty());
+    m.insert(3, [7; 1 << 11]);
+    assert_eq!(7, m[3][1000]);
+}
+
 #[test]
 fn clears_it_up() {
     let mut m: Map<&str> = Map::with_capacity_none(16);
@@ -283,6 +2017,37 @@ fn clears_it_up() {
     assert_eq!(0, m.len());
 }
 
+#[test]
+fn forgets_values_on_clear() {
+    let mut m: Map<&str> = Map::with_capacity_none(16);
+    m.insert(7, "one");
+    m.clear();
+    assert!(!m.contains_key(7));
+    assert!(m.get(7).is_none());
+}
+
+#[test]
+fn drops_values_on_clear() {
+    use std::rc::Rc;
+    let v = Rc::new(());
+    let mut m: Map<Rc<()>> = Map::with_capacity_none(8);
+    for k in [0, 2, 5, 7] {
+        m.insert(k, Rc::clone(&v));
+    }
+    m.clear();
+    assert!(m.is_empty());
+    assert_eq!(1, Rc::strong_count(&v));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "Can't clear() non-initialized Map")]
+fn refuses_to_clear_non_initialized() {
+    let mut m: Map<String> = Map::with_capacity(8);
+    m.insert(3, "three".to_string());
+    m.clear();
+}
+
 #[test]
 fn pushes_into() {
     let mut m: Map<&str> = Map::with_capacity_none(16);
@@ -291,7 +2056,51 @@ fn pushes_into() {
 }
 
 #[test]
-#[should_panic]
+fn pushes_into_gap() {
+    let mut m: Map<&str> = Map::with_capacity_none(16);
+    m.push("zero");
+    m.push("one");
+    m.push("two");
+    m.remove(1);
+    assert_eq!(1, m.push("again"));
+}
+
+#[test]
+fn pushes_into_scattered_gaps_ascending() {
+    let mut m: Map<usize> = Map::with_capacity_none(16);
+    for i in 0..10 {
+        m.push(i);
+    }
+    for k in [7, 2, 9, 4, 0] {
+        m.remove(k);
+    }
+    let keys: Vec<usize> = (0..6).map(|i| m.push(i)).collect();
+    assert_eq!(vec![0, 2, 4, 7, 9, 10], keys);
+}
+
+#[test]
+fn pushes_back_over_gap() {
+    let mut m: Map<&str> = Map::with_capacity_none(16);
+    m.push("zero");
+    m.push("one");
+    m.push("two");
+    m.remove(1);
+    assert_eq!(3, m.push_back("three"));
+    m.remove(3);
+    m.remove(2);
+    assert_eq!(1, m.push_back("one"));
+}
+
+#[test]
+#[should_panic(expected = "No more keys available left")]
+fn pushes_back_beyond_boundary() {
+    let mut m: Map<&str> = Map::with_capacity_none(2);
+    m.insert(1, "one");
+    m.push_back("two");
+}
+
+#[test]
+#[should_panic(expected = "The key 5 is over the boundary 1")]
 #[cfg(debug_assertions)]
 fn insert_out_of_boundary() {
     let mut m: Map<&str> = Map::with_capacity(1);
@@ -299,7 +2108,7 @@ fn insert_out_of_boundary() {
 }
 
 #[test]
-#[should_panic]
+#[should_panic(expected = "The key 5 is over the boundary 1")]
 #[cfg(debug_assertions)]
 fn get_out_of_boundary() {
     let m: Map<&str> = Map::with_capacity(1);
@@ -307,7 +2116,7 @@ fn get_out_of_boundary() {
 }
 
 #[test]
-#[should_panic]
+#[should_panic(expected = "The key 5 is over the boundary 1")]
 #[cfg(debug_assertions)]
 fn remove_out_of_boundary() {
     let mut m: Map<&str> = Map::with_capacity(1);
@@ -18,11 +18,15 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use crate::Map;
+use crate::{raw, Map, MapFullError};
 
 impl<V: Clone> Map<V> {
     /// Get the next key available for insertion.
     ///
+    /// It is always the smallest key that is not occupied. The keys are
+    /// scanned from zero, so the cost is proportional to the number of
+    /// occupied keys below the returned one.
+    ///
     /// # Panics
     ///
     /// If no more keys left.
@@ -43,26 +47,44 @@ impl<V: Clone> Map<V> {
     #[inline]
     #[must_use]
     pub fn next_key_gte(&self, k: usize) -> usize {
+        self.try_next_key_gte(k)
+            .expect("No more keys available left")
+    }
+
+    /// Get the next key available for insertion, just like
+    /// [`Map::next_key`], or an error if no more keys left.
+    ///
+    /// # Errors
+    ///
+    /// If all keys are occupied.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in "debug" mode if the Map is not initialized.
+    #[inline]
+    pub fn try_next_key(&self) -> Result<usize, MapFullError> {
+        self.try_next_key_gte(0)
+    }
+
+    /// Get the next key available for insertion, which is "greater or equal"
+    /// than the number provided, or an error if no more keys left.
+    ///
+    /// # Errors
+    ///
+    /// If all keys from the number provided up to the capacity
+    /// are occupied.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in "debug" mode if the Map is not initialized.
+    #[inline]
+    pub fn try_next_key_gte(&self, k: usize) -> Result<usize, MapFullError> {
         #[cfg(debug_assertions)]
         assert!(
             self.initialized,
             "Can't do next_key_gte() on non-initialized Map"
         );
-        let mut i = k;
-        loop {
-            if i == self.max {
-                break;
-            }
-            if i > self.max {
-                return i;
-            }
-            if self.get(i).is_none() {
-                return i;
-            }
-            i += 1;
-        }
-        assert_ne!(self.max, self.layout.size(), "No more keys available left");
-        self.max
+        unsafe { raw::next_free(self.head, k, self.max, self.capacity()) }.ok_or(MapFullError)
     }
 }
 
@@ -82,6 +104,17 @@ fn get_next_in_the_middle() {
     assert_eq!(1, m.next_key());
 }
 
+#[test]
+fn get_lowest_free_key() {
+    let mut m: Map<u32> = Map::with_capacity_none(16);
+    m.insert(0, 42);
+    m.insert(1, 42);
+    m.insert(2, 42);
+    m.remove(2);
+    m.remove(0);
+    assert_eq!(0, m.next_key());
+}
+
 #[test]
 fn get_next_over() {
     let mut m: Map<u32> = Map::with_capacity_none(16);
@@ -99,9 +132,20 @@ fn reset_next_key_on_clear() {
 }
 
 #[test]
-#[should_panic]
+#[should_panic(expected = "No more keys available left")]
 fn panics_on_end_of_keys() {
     let mut m: Map<u32> = Map::with_capacity_none(1);
     m.insert(0, 42);
     assert_ne!(1, m.next_key());
 }
+
+#[test]
+fn fails_to_find_key_in_full_map() {
+    let mut m: Map<u32> = Map::with_capacity_none(3);
+    m.insert(0, 42);
+    m.insert(2, 42);
+    assert_eq!(Ok(1), m.try_next_key());
+    assert_eq!(Err(MapFullError), m.try_next_key_gte(2));
+    m.insert(1, 42);
+    assert_eq!(Err(MapFullError), m.try_next_key());
+}
@@ -0,0 +1,74 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::Map;
+use std::cmp::Ordering;
+
+impl<V: Clone + PartialOrd> PartialOrd for Map<V> {
+    /// The maps are compared as sequences of `(key, value)` pairs,
+    /// in ascending order of keys.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<V: Clone + Ord> Ord for Map<V> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+#[cfg(test)]
+fn map_of(pairs: &[(usize, u32)]) -> Map<u32> {
+    let mut m = Map::with_capacity_none(8);
+    for (k, v) in pairs {
+        m.insert(*k, *v);
+    }
+    m
+}
+
+#[test]
+fn orders_by_values() {
+    assert!(map_of(&[(0, 1), (2, 5)]) < map_of(&[(0, 1), (2, 6)]));
+}
+
+#[test]
+fn orders_by_keys() {
+    assert!(map_of(&[(0, 9), (2, 1)]) < map_of(&[(0, 9), (3, 1)]));
+    assert!(map_of(&[(0, 9)]) < map_of(&[(0, 9), (3, 1)]));
+    assert!(map_of(&[(1, 0)]) > map_of(&[(0, 9), (3, 1)]));
+}
+
+#[test]
+fn ignores_insertion_order() {
+    let a = map_of(&[(5, 1), (0, 2), (3, 3)]);
+    let b = map_of(&[(0, 2), (3, 3), (5, 1)]);
+    assert_eq!(Ordering::Equal, a.cmp(&b));
+}
+
+#[test]
+fn sorts_maps() {
+    let mut maps = vec![map_of(&[(1, 1)]), map_of(&[(0, 2)]), map_of(&[])];
+    maps.sort();
+    assert_eq!(
+        vec![map_of(&[]), map_of(&[(0, 2)]), map_of(&[(1, 1)])],
+        maps
+    );
+}
@@ -0,0 +1,233 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::Map;
+use std::ptr;
+
+impl<V: Clone> Map<V> {
+    /// Get the insertion sequence number of the key, or `None` if the key
+    /// is absent.
+    ///
+    /// Every insertion, including an overwrite of an existing key, gets
+    /// a number bigger than all the previous ones, so the numbers of two
+    /// keys tell which of them was inserted later.
+    ///
+    /// # Panics
+    ///
+    /// It may panic if you attempt to refer to they key that is outside
+    /// of the boundary of this map. It will not return `None`, it will panic.
+    /// However, in "release" mode it will not panic, but will lead to
+    /// undefined behavior.
+    #[inline]
+    #[must_use]
+    pub fn insertion_seq(&self, k: usize) -> Option<u64> {
+        if self.contains_key(k) {
+            Some(self.seqs[k])
+        } else {
+            None
+        }
+    }
+
+    /// Get the key that was inserted before all others, or `None` if
+    /// the map is empty.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    #[must_use]
+    pub fn oldest_key(&self) -> Option<usize> {
+        self.keys().min_by_key(|k| self.seqs[*k])
+    }
+
+    /// Get the key that was inserted after all others, or `None` if
+    /// the map is empty.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    #[must_use]
+    pub fn newest_key(&self) -> Option<usize> {
+        self.keys().max_by_key(|k| self.seqs[*k])
+    }
+
+    /// Remove the key that was inserted before all others and return it
+    /// together with its value, or `None` if the map is empty.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    pub fn evict_oldest(&mut self) -> Option<(usize, V)> {
+        let k = self.oldest_key()?;
+        let v = unsafe { ptr::replace(self.head.add(k), None) };
+        self.narrow(k);
+        v.map(|v| (k, v))
+    }
+
+    /// Push to the smallest available key, evicting the oldest key first
+    /// if the map is full, which makes it a simple bounded cache.
+    ///
+    /// Returns the key of the new value and the evicted pair, if any.
+    ///
+    /// # Panics
+    ///
+    /// If the capacity of the map is zero.
+    ///
+    /// It may also panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    pub fn push_evicting(&mut self, v: V) -> (usize, Option<(usize, V)>) {
+        let evicted = if self.len() == self.capacity() {
+            self.evict_oldest()
+        } else {
+            None
+        };
+        (self.push(v), evicted)
+    }
+
+    /// Take the insertion sequence numbers from another map, which
+    /// has the same keys.
+    #[inline]
+    pub(crate) fn copy_stamps(&mut self, seqs: &[u64], seq: u64) {
+        self.seqs.copy_from_slice(seqs);
+        self.seq = seq;
+    }
+}
+
+#[test]
+fn tracks_oldest_and_newest() {
+    let mut m: Map<&str> = Map::with_capacity_none(8);
+    assert_eq!(None, m.oldest_key());
+    m.insert(5, "a");
+    m.insert(2, "b");
+    m.insert(7, "c");
+    assert_eq!(Some(5), m.oldest_key());
+    assert_eq!(Some(7), m.newest_key());
+    assert!(m.insertion_seq(5) < m.insertion_seq(2));
+    assert_eq!(None, m.insertion_seq(0));
+}
+
+#[test]
+fn overwrite_updates_sequence() {
+    let mut m: Map<&str> = Map::with_capacity_none(8);
+    m.insert(5, "a");
+    m.insert(2, "b");
+    m.insert(5, "c");
+    assert_eq!(Some(2), m.oldest_key());
+    assert_eq!(Some(5), m.newest_key());
+}
+
+#[test]
+fn entry_overwrite_updates_sequence() {
+    use crate::Entry;
+    let mut m: Map<u32> = Map::with_capacity_none(8);
+    m.insert(1, 1);
+    m.insert(2, 2);
+    if let Entry::Occupied(mut e) = m.entry(1) {
+        assert_eq!(1, e.insert(10));
+    }
+    assert_eq!(Some(1), m.newest_key());
+    assert_eq!(Some(2), m.oldest_key());
+}
+
+#[test]
+fn push_and_remove_keep_order() {
+    let mut m: Map<u32> = Map::with_capacity_none(4);
+    m.push(1);
+    m.push(2);
+    m.push(3);
+    m.remove(0);
+    assert_eq!(Some(1), m.oldest_key());
+    assert_eq!(0, m.push(4));
+    assert_eq!(Some(0), m.newest_key());
+}
+
+#[test]
+fn keeps_sequence_through_moves_and_clones() {
+    let mut m: Map<u32> = Map::with_capacity_none(8);
+    m.insert(6, 1);
+    m.insert(1, 2);
+    m.relocate(6, 3);
+    assert_eq!(Some(3), m.oldest_key());
+    let c = m.clone();
+    assert_eq!(Some(3), c.oldest_key());
+    m.compact_keys();
+    assert_eq!(Some(1), m.oldest_key());
+    assert_eq!(Some(0), m.newest_key());
+}
+
+#[test]
+fn evicts_oldest() {
+    let mut m: Map<&str> = Map::with_capacity_none(8);
+    assert_eq!(None, m.evict_oldest());
+    m.insert(4, "a");
+    m.insert(1, "b");
+    assert_eq!(Some((4, "a")), m.evict_oldest());
+    assert_eq!(Some((1, "b")), m.evict_oldest());
+    assert!(m.is_empty());
+}
+
+#[test]
+fn pushes_evicting_on_full_map() {
+    let mut m: Map<u32> = Map::with_capacity_none(3);
+    assert_eq!((0, None), m.push_evicting(10));
+    assert_eq!((1, None), m.push_evicting(11));
+    assert_eq!((2, None), m.push_evicting(12));
+    m.insert(0, 20);
+    assert_eq!((1, Some((1, 11))), m.push_evicting(21));
+    assert_eq!((2, Some((2, 12))), m.push_evicting(22));
+    assert_eq!(vec![20, 21, 22], m.values().copied().collect::<Vec<_>>());
+    assert_eq!(Some(0), m.oldest_key());
+}
+
+#[test]
+#[should_panic(expected = "No more keys available left")]
+fn push_evicting_panics_on_zero_capacity() {
+    let mut m: Map<u32> = Map::with_capacity_none(0);
+    m.push_evicting(1);
+}
+
+#[test]
+fn displays_in_insertion_order() {
+    let mut m: Map<&str> = Map::with_capacity_none(8);
+    m.insert(5, "a");
+    m.insert(2, "b");
+    m.insert(7, "c");
+    assert_eq!("{2: b, 5: a, 7: c}", format!("{m}"));
+    assert_eq!("{5: a, 2: b, 7: c}", m.display_unsorted().to_string());
+    m.insert(2, "d");
+    assert_eq!("{5: a, 7: c, 2: d}", m.display_unsorted().to_string());
+}
+
+#[test]
+fn loses_insertion_order_in_raw_parts() {
+    let mut m: Map<&str> = Map::with_capacity_none(8);
+    m.insert(6, "a");
+    m.insert(3, "b");
+    assert_eq!(Some(6), m.oldest_key());
+    let (head, layout, max) = m.into_raw_parts();
+    let mut m = unsafe { Map::from_raw_parts(head, layout, max) };
+    assert_eq!(vec![3, 6], m.keys().collect::<Vec<_>>());
+    assert_eq!(Some(3), m.oldest_key());
+    m.insert(1, "c");
+    assert_eq!(Some(1), m.newest_key());
+    assert_eq!(Some((3, "b")), m.evict_oldest());
+}
@@ -0,0 +1,84 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::Map;
+use rayon::iter::{Enumerate, FilterMap, IndexedParallelIterator, IntoParallelIterator};
+use rayon::prelude::ParallelIterator;
+use rayon::slice::Iter;
+use std::slice;
+
+type Occupied<'a, V> = fn((usize, &'a Option<V>)) -> Option<(usize, &'a V)>;
+
+impl<'a, V: Clone + Sync + 'a> IntoParallelIterator for &'a Map<V> {
+    type Iter = FilterMap<Enumerate<Iter<'a, Option<V>>>, Occupied<'a, V>>;
+    type Item = (usize, &'a V);
+
+    /// Split the used range of slots into chunks and walk them in parallel,
+    /// yielding only the occupied ones.
+    #[inline]
+    fn into_par_iter(self) -> Self::Iter {
+        #[cfg(debug_assertions)]
+        assert!(
+            self.initialized,
+            "Can't into_par_iter() non-initialized Map"
+        );
+        let slots = unsafe { slice::from_raw_parts(self.head, self.max) };
+        slots
+            .into_par_iter()
+            .enumerate()
+            .filter_map(|(k, v)| v.as_ref().map(|v| (k, v)))
+    }
+}
+
+impl<V: Clone + Sync> Map<V> {
+    /// Make a parallel iterator over all values.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    #[must_use]
+    pub fn par_values(&self) -> impl ParallelIterator<Item = &V> {
+        self.into_par_iter().map(|(_, v)| v)
+    }
+}
+
+#[cfg(test)]
+use rayon::iter::IntoParallelRefIterator;
+
+#[test]
+fn sums_in_parallel() {
+    let mut m: Map<u64> = Map::with_capacity_none(10_000);
+    for k in (0..10_000).step_by(3) {
+        m.insert(k, k as u64);
+    }
+    let seq: u64 = m.values().sum();
+    assert_eq!(seq, m.par_values().sum::<u64>());
+    assert_eq!(seq, m.par_iter().map(|(_, v)| *v).sum::<u64>());
+}
+
+#[test]
+fn collects_keys_in_parallel() {
+    let mut m: Map<&str> = Map::with_capacity_none(16);
+    m.insert(2, "two");
+    m.insert(9, "nine");
+    let keys: Vec<usize> = m.par_iter().map(|(k, _)| k).collect();
+    assert_eq!(vec![2, 9], keys);
+}
@@ -0,0 +1,70 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// The logic of the slots, shared by `Map` and `ArrayMap`, which only
+// differ in where the slots are kept: on the heap or inline. The caller
+// guarantees that the keys are inside the boundary and that the slots
+// below `max` are initialized.
+
+use std::ptr;
+
+/// Count the occupied slots below `max`.
+#[inline]
+pub unsafe fn len<V>(head: *const Option<V>, max: usize) -> usize {
+    (0..max).filter(|k| (*head.add(*k)).is_some()).count()
+}
+
+/// Find the smallest free key, which is "greater or equal" than `k`,
+/// or `None` if all keys from `k` to `cap` are occupied.
+#[inline]
+pub unsafe fn next_free<V>(
+    head: *const Option<V>,
+    k: usize,
+    max: usize,
+    cap: usize,
+) -> Option<usize> {
+    if k > max {
+        return Some(k);
+    }
+    (k..max)
+        .find(|i| (*head.add(*i)).is_none())
+        .or_else(|| (max < cap).then_some(max))
+}
+
+/// Put the value into the slot, without dropping the value that may
+/// be there already.
+#[inline]
+pub const unsafe fn insert<V>(head: *mut Option<V>, k: usize, v: V) {
+    ptr::write(head.add(k), Some(v));
+}
+
+/// Empty the slot, without dropping the value that may be there.
+#[inline]
+pub const unsafe fn remove<V>(head: *mut Option<V>, k: usize) {
+    ptr::write(head.add(k), None);
+}
+
+/// Empty all slots below `max`, dropping their values.
+#[inline]
+pub unsafe fn clear<V>(head: *mut Option<V>, max: usize) {
+    for k in 0..max {
+        *head.add(k) = None;
+    }
+}
@@ -18,8 +18,8 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use crate::Map;
-use serde::de::{MapAccess, Visitor};
+use crate::{AllocError, Map};
+use serde::de::{Error, MapAccess, Visitor};
 use serde::ser::SerializeMap;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::HashMap;
@@ -41,6 +41,40 @@ impl<V: Clone + Serialize> Serialize for Map<V> {
     }
 }
 
+/// A key, which human-readable formats, like JSON, may keep as a string.
+struct Key(usize);
+
+struct KeyVi;
+
+impl Visitor<'_> for KeyVi {
+    type Value = Key;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a usize key, maybe as a string")
+    }
+
+    fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
+        usize::try_from(v).map(Key).map_err(E::custom)
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        v.parse().map(Key).map_err(E::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for Key {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(KeyVi)
+        } else {
+            usize::deserialize(deserializer).map(Key)
+        }
+    }
+}
+
 struct Vi<V>(PhantomData<V>);
 
 impl<'de, V: Clone + Deserialize<'de>> Visitor<'de> for Vi<V> {
@@ -55,17 +89,28 @@ impl<'de, V: Clone + Deserialize<'de>> Visitor<'de> for Vi<V> {
         M: MapAccess<'de>,
     {
         let mut map: HashMap<usize, V> = HashMap::new();
-        while let Some((key, value)) = access.next_entry()? {
+        while let Some((Key(key), value)) = access.next_entry()? {
             map.insert(key, value);
         }
-        let mut m: Self::Value = Map::with_capacity_none(map.len());
-        for (k, v) in map.iter() {
+        let cap = Map::<V>::capacity_for_keys(map.keys().copied()).map_err(M::Error::custom)?;
+        let mut m: Self::Value = Map::try_with_capacity_none(cap).map_err(alloc_failed)?;
+        for (k, v) in &map {
             m.insert(*k, v.clone());
         }
         Ok(m)
     }
 }
 
+/// Turn a failed allocation into a deserialization error, instead of
+/// a panic or an abort.
+///
+/// A capacity that fits into a memory layout is still allocated and
+/// filled with `None`, so the memory it takes is only limited by
+/// the largest key in the data.
+fn alloc_failed<E: Error>(e: AllocError) -> E {
+    E::custom(e)
+}
+
 impl<'de, V: Clone + Deserialize<'de>> Deserialize<'de> for Map<V> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -98,3 +143,70 @@ fn serde_big_map() {
     let after: Map<u8> = deserialize(&bytes).unwrap();
     assert_eq!(2, after.capacity());
 }
+
+#[test]
+fn deserialize_sparse_keys() {
+    let mut before: Map<u8> = Map::with_capacity_none(16);
+    before.insert(3, 42);
+    before.insert(7, 16);
+    let bytes: Vec<u8> = serialize(&before).unwrap();
+    let after: Map<u8> = deserialize(&bytes).unwrap();
+    assert_eq!(8, after.capacity());
+    assert_eq!(16, *after.get(7).unwrap());
+}
+
+#[test]
+fn rejects_overflowing_key() {
+    let mut entries: HashMap<usize, u8> = HashMap::new();
+    entries.insert(usize::MAX, 42);
+    let bytes: Vec<u8> = serialize(&entries).unwrap();
+    assert!(deserialize::<Map<u8>>(&bytes).is_err());
+}
+
+#[test]
+fn json_round_trip() {
+    let mut before: Map<u8> = Map::with_capacity_none(4);
+    before.insert(0, 42);
+    before.insert(2, 7);
+    let json = serde_json::to_string(&before).unwrap();
+    assert_eq!("{\"0\":42,\"2\":7}", json);
+    let after: Map<u8> = serde_json::from_str(&json).unwrap();
+    assert_eq!(2, after.len());
+    assert_eq!(7, after[2]);
+}
+
+#[test]
+fn json_rejects_reserved_key() {
+    assert!(serde_json::from_str::<Map<u8>>("{\"18446744073709551615\": 1}").is_err());
+}
+
+#[test]
+fn json_rejects_non_numeric_key() {
+    assert!(serde_json::from_str::<Map<u8>>("{\"one\": 1}").is_err());
+}
+
+#[test]
+fn rejects_unreasonable_capacity() {
+    let mut entries: HashMap<usize, u64> = HashMap::new();
+    entries.insert(usize::MAX - 1, 42);
+    let bytes: Vec<u8> = serialize(&entries).unwrap();
+    let err = deserialize::<Map<u64>>(&bytes).err().unwrap();
+    assert!(err.to_string().contains("The capacity is too big"));
+}
+
+#[test]
+fn reports_out_of_memory_as_error() {
+    let err: serde_json::Error = alloc_failed(AllocError::OutOfMemory);
+    assert_eq!("Out of memory", err.to_string());
+    let err: serde_json::Error = alloc_failed(AllocError::CapacityOverflow);
+    assert_eq!("The capacity is too big", err.to_string());
+}
+
+#[test]
+fn rejects_key_that_fits_no_memory() {
+    let mut entries: HashMap<usize, u64> = HashMap::new();
+    entries.insert(Map::<u64>::MAX_CAPACITY - 1, 42);
+    let bytes: Vec<u8> = serialize(&entries).unwrap();
+    let err = deserialize::<Map<u64>>(&bytes).err().unwrap();
+    assert!(err.to_string().contains("Out of memory"));
+}
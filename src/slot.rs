@@ -0,0 +1,135 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::{Map, Slot};
+
+impl<V: Clone> Map<V> {
+    /// Get a handle to the key, checking the boundary only once.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use emap::Map;
+    /// let mut m: Map<u32> = Map::with_capacity_none(16);
+    /// let mut s = m.slot(7);
+    /// s.set(1);
+    /// *s.get_mut().unwrap() += 1;
+    /// assert_eq!(Some(2), s.take());
+    /// assert!(!s.is_occupied());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the key is outside of the boundary of this map, both in "debug"
+    /// and "release" modes.
+    #[inline]
+    pub fn slot(&mut self, k: usize) -> Slot<'_, V> {
+        assert!(
+            k < self.capacity(),
+            "The key {k} is over the boundary {}",
+            self.capacity()
+        );
+        #[cfg(debug_assertions)]
+        assert!(self.initialized, "Can't slot() non-initialized Map");
+        Slot { key: k, map: self }
+    }
+}
+
+impl<V: Clone> Slot<'_, V> {
+    /// Get the key of this slot.
+    #[inline]
+    #[must_use]
+    pub const fn key(&self) -> usize {
+        self.key
+    }
+
+    /// Is there a value in it?
+    #[inline]
+    #[must_use]
+    pub const fn is_occupied(&self) -> bool {
+        self.get().is_some()
+    }
+
+    /// Get a reference to the value.
+    #[inline]
+    #[must_use]
+    pub const fn get(&self) -> Option<&V> {
+        unsafe { &*self.map.head.add(self.key) }.as_ref()
+    }
+
+    /// Get a mutable reference to the value.
+    #[inline]
+    pub const fn get_mut(&mut self) -> Option<&mut V> {
+        unsafe { &mut *self.map.head.add(self.key) }.as_mut()
+    }
+
+    /// Put the value into it, returning the one that was there before.
+    #[inline]
+    pub fn set(&mut self, v: V) -> Option<V> {
+        self.map.widen(self.key);
+        self.map.stamp(self.key);
+        unsafe { &mut *self.map.head.add(self.key) }.replace(v)
+    }
+
+    /// Take the value out of it, leaving it empty.
+    #[inline]
+    pub fn take(&mut self) -> Option<V> {
+        let v = unsafe { &mut *self.map.head.add(self.key) }.take();
+        self.map.narrow(self.key);
+        v
+    }
+}
+
+#[test]
+fn works_like_direct_calls() {
+    let mut direct: Map<u32> = Map::with_capacity_none(8);
+    direct.insert(5, 1);
+    direct.insert(5, 2);
+    direct.remove(5);
+    direct.insert(5, 3);
+    let mut m: Map<u32> = Map::with_capacity_none(8);
+    {
+        let mut s = m.slot(5);
+        assert_eq!(5, s.key());
+        assert_eq!(None, s.set(1));
+        assert_eq!(Some(1), s.set(2));
+        assert_eq!(Some(2), s.take());
+        assert!(!s.is_occupied());
+        s.set(3);
+        assert_eq!(Some(&3), s.get());
+    }
+    assert_eq!(direct, m);
+    assert_eq!(Some(5), m.last_key());
+    assert_eq!(vec![5], m.keys().collect::<Vec<_>>());
+}
+
+#[test]
+fn takes_from_empty_slot() {
+    let mut m: Map<String> = Map::with_capacity_none(2);
+    assert_eq!(None, m.slot(1).take());
+    assert!(m.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "The key 2 is over the boundary 2")]
+fn panics_on_slot_over_boundary() {
+    let mut m: Map<u32> = Map::with_capacity_none(2);
+    m.slot(2).set(1);
+}
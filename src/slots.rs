@@ -0,0 +1,239 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::Map;
+use std::ops::RangeBounds;
+use std::{ptr, slice};
+
+impl<V: Clone> Map<V> {
+    /// Get all slots of the map as a slice, where the position in the
+    /// slice is the key.
+    ///
+    /// For example:
+    ///
+    /// ```
+    /// use emap::Map;
+    /// let mut m: Map<&str> = Map::with_capacity_none(4);
+    /// m.insert(2, "two");
+    /// assert_eq!(&[None, None, Some("two"), None], m.as_option_slice());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    #[must_use]
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn as_option_slice(&self) -> &[Option<V>] {
+        #[cfg(debug_assertions)]
+        assert!(
+            self.initialized,
+            "Can't as_option_slice() non-initialized Map"
+        );
+        unsafe { slice::from_raw_parts(self.head, self.capacity()) }
+    }
+
+    /// Turn it into a vector of all slots, moving the values out,
+    /// where the position in the vector is the key.
+    ///
+    /// It is the inverse of `Map::try_from(Vec<Option<V>>)`.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    #[must_use]
+    pub fn into_vec(self) -> Vec<Option<V>> {
+        #[cfg(debug_assertions)]
+        assert!(self.initialized, "Can't into_vec() non-initialized Map");
+        (0..self.capacity())
+            .map(|k| unsafe { ptr::replace(self.head.add(k), None) })
+            .collect()
+    }
+
+    /// Turn it into a vector of the occupied keys and their values,
+    /// in ascending order of keys, moving the values out.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    #[must_use]
+    pub fn into_sorted_vec(self) -> Vec<(usize, V)> {
+        #[cfg(debug_assertions)]
+        assert!(
+            self.initialized,
+            "Can't into_sorted_vec() non-initialized Map"
+        );
+        (0..self.max)
+            .filter_map(|k| unsafe { ptr::replace(self.head.add(k), None) }.map(|v| (k, v)))
+            .collect()
+    }
+
+    /// Clear the buffer and fill it with the occupied keys and clones
+    /// of their values, in ascending order of keys.
+    ///
+    /// The buffer is not reallocated if its capacity is big enough, so
+    /// it may be reused as a scratch space for many maps.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    pub fn copy_entries_into(&self, buf: &mut Vec<(usize, V)>) {
+        buf.clear();
+        buf.extend(self.iter().map(|(k, v)| (k, v.clone())));
+    }
+
+    /// Make an iterator over the slots in the range, both occupied
+    /// and empty ones, reading them directly.
+    ///
+    /// The range is clamped to the capacity of the map. The cost is
+    /// proportional to the width of the range, no matter how many
+    /// keys are occupied elsewhere.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    pub fn slots<R: RangeBounds<usize>>(
+        &self,
+        range: R,
+    ) -> impl DoubleEndedIterator<Item = (usize, Option<&V>)> {
+        let (start, end) = Self::bounds(&range);
+        let end = end.min(self.capacity());
+        let start = start.min(end);
+        self.as_option_slice()[start..end]
+            .iter()
+            .enumerate()
+            .map(move |(i, s)| (start + i, s.as_ref()))
+    }
+}
+
+#[test]
+fn shows_gaps_in_slice() {
+    let mut m: Map<u32> = Map::with_capacity_none(6);
+    m.insert(1, 10);
+    m.insert(4, 40);
+    assert_eq!(
+        &[None, Some(10), None, None, Some(40), None],
+        m.as_option_slice()
+    );
+}
+
+#[test]
+fn shows_empty_slice() {
+    let m: Map<u32> = Map::with_capacity_none(0);
+    assert!(m.as_option_slice().is_empty());
+}
+
+#[test]
+fn shows_no_values_after_clear() {
+    let mut m: Map<u32> = Map::with_capacity_none(3);
+    m.insert(2, 42);
+    m.clear();
+    assert!(m.as_option_slice().iter().all(Option::is_none));
+}
+
+#[test]
+fn scans_narrow_range_of_slots() {
+    let mut m: Map<u32> = Map::with_capacity_none(1 << 16);
+    m.insert(10, 1);
+    m.insert(5000, 2);
+    m.insert(5003, 3);
+    m.insert(60000, 4);
+    assert_eq!(
+        vec![
+            (5000, Some(&2)),
+            (5001, None),
+            (5002, None),
+            (5003, Some(&3))
+        ],
+        m.slots(5000..=5003).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn clamps_slots_to_capacity() {
+    let mut m: Map<u32> = Map::with_capacity_none(4);
+    m.insert(3, 3);
+    assert_eq!(vec![(3, Some(&3))], m.slots(3..100).collect::<Vec<_>>());
+    assert_eq!(4, m.slots(..).count());
+    assert_eq!(0, m.slots(7..9).count());
+}
+
+#[test]
+fn turns_into_vec() {
+    let mut m: Map<String> = Map::with_capacity_none(5);
+    m.insert(1, "one".to_string());
+    m.insert(3, "three".to_string());
+    assert_eq!(
+        vec![
+            None,
+            Some("one".to_string()),
+            None,
+            Some("three".to_string()),
+            None
+        ],
+        m.into_vec()
+    );
+}
+
+#[test]
+fn round_trips_through_vec() {
+    let slots = vec![Some(1), None, None, Some(4)];
+    let m: Map<u32> = Map::try_from(slots.clone()).unwrap();
+    assert_eq!(slots, m.into_vec());
+}
+
+#[test]
+fn turns_into_sorted_vec() {
+    let mut m: Map<String> = Map::with_capacity_none(16);
+    for k in [9, 2, 14, 5] {
+        m.insert(k, k.to_string());
+    }
+    m.remove(14);
+    assert_eq!(
+        vec![
+            (2, "2".to_string()),
+            (5, "5".to_string()),
+            (9, "9".to_string())
+        ],
+        m.into_sorted_vec()
+    );
+}
+
+#[test]
+fn copies_entries_into_reused_buffer() {
+    let mut a: Map<u32> = Map::with_capacity_none(16);
+    for k in [7, 1, 12] {
+        a.insert(k, u32::try_from(k).unwrap());
+    }
+    let mut b: Map<u32> = Map::with_capacity_none(4);
+    b.insert(3, 33);
+    let mut buf = Vec::with_capacity(8);
+    let ptr = buf.as_ptr();
+    a.copy_entries_into(&mut buf);
+    assert_eq!(vec![(1, 1), (7, 7), (12, 12)], buf);
+    b.copy_entries_into(&mut buf);
+    assert_eq!(vec![(3, 33)], buf);
+    assert_eq!(ptr, buf.as_ptr());
+    assert_eq!(8, buf.capacity());
+}
@@ -0,0 +1,235 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::Map;
+use std::io;
+use std::io::{Read, Write};
+
+/// Write the number as a LEB128 varint.
+#[allow(clippy::cast_possible_truncation)]
+fn write_varint<W: Write>(w: &mut W, mut n: usize) -> io::Result<()> {
+    loop {
+        let byte = (n & 0x7F) as u8;
+        n >>= 7;
+        if n == 0 {
+            return w.write_all(&[byte]);
+        }
+        w.write_all(&[byte | 0x80])?;
+    }
+}
+
+/// Read a LEB128 varint, refusing the ones that don't fit into `usize`.
+fn read_varint<R: Read>(r: &mut R) -> io::Result<usize> {
+    let mut n: usize = 0;
+    let mut shift = 0;
+    loop {
+        let mut byte = [0; 1];
+        r.read_exact(&mut byte)?;
+        let low = usize::from(byte[0] & 0x7F);
+        if shift >= usize::BITS || (low << shift) >> shift != low {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "The varint is too big",
+            ));
+        }
+        n |= low << shift;
+        if byte[0] & 0x80 == 0 {
+            return Ok(n);
+        }
+        shift += 7;
+    }
+}
+
+impl<V: Clone> Map<V> {
+    /// Write the map in a compact binary format, without serde.
+    ///
+    /// The capacity and the number of items go first, then each key
+    /// followed by its value, in ascending order of keys. The numbers are
+    /// LEB128 varints, while the values are written by the function
+    /// provided. Use [`Map::read_entries`] to read it back.
+    ///
+    /// # Errors
+    ///
+    /// If the writer or the function fails.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    pub fn write_entries<W: Write, F: FnMut(&V, &mut W) -> io::Result<()>>(
+        &self,
+        w: &mut W,
+        mut f: F,
+    ) -> io::Result<()> {
+        write_varint(w, self.capacity())?;
+        write_varint(w, self.len())?;
+        for (k, v) in self.iter() {
+            write_varint(w, k)?;
+            f(v, w)?;
+        }
+        Ok(())
+    }
+
+    /// Read the map written by [`Map::write_entries`], reading
+    /// the values with the function provided.
+    ///
+    /// The capacity declared in the data is checked against `max_capacity`
+    /// before anything is allocated, so a hostile input can't make it
+    /// reserve an unreasonable amount of memory.
+    ///
+    /// # Errors
+    ///
+    /// If the reader or the function fails, if the data is broken, if
+    /// the declared capacity is over `max_capacity`, if a key is met
+    /// twice, or if the map can't be allocated.
+    #[inline]
+    pub fn read_entries<R: Read, F: FnMut(&mut R) -> io::Result<V>>(
+        r: &mut R,
+        max_capacity: usize,
+        mut f: F,
+    ) -> io::Result<Self> {
+        let cap = read_varint(r)?;
+        if cap > max_capacity {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("The capacity {cap} is over the limit {max_capacity}"),
+            ));
+        }
+        let len = read_varint(r)?;
+        if len > cap {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("There are {len} items in the Map of capacity {cap}"),
+            ));
+        }
+        let mut map = Self::try_with_capacity_none(cap)
+            .map_err(|e| io::Error::new(io::ErrorKind::OutOfMemory, e))?;
+        for _ in 0..len {
+            let key = read_varint(r)?;
+            map.check_boundaries(key)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            if map.contains_key(key) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("The key {key} is met twice"),
+                ));
+            }
+            let value = f(r)?;
+            map.insert(key, value);
+        }
+        Ok(map)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn write_u32(v: &u32, w: &mut Vec<u8>) -> io::Result<()> {
+    w.write_all(&v.to_le_bytes())
+}
+
+#[cfg(test)]
+fn read_u32(r: &mut &[u8]) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    r.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+#[test]
+fn round_trips_entries() {
+    let mut before: Map<u32> = Map::with_capacity_none(300);
+    before.insert(1, 10);
+    before.insert(200, 20);
+    before.insert(299, 30);
+    let mut bytes = vec![];
+    before.write_entries(&mut bytes, write_u32).unwrap();
+    assert_eq!(2 + 1 + 3 * 4 + 1 + 2 + 2, bytes.len());
+    let after: Map<u32> = Map::read_entries(&mut bytes.as_slice(), 1000, read_u32).unwrap();
+    assert_eq!(300, after.capacity());
+    assert_eq!(before, after);
+}
+
+#[test]
+fn round_trips_empty_map() {
+    let before: Map<u32> = Map::with_capacity_none(0);
+    let mut bytes = vec![];
+    before.write_entries(&mut bytes, write_u32).unwrap();
+    let after: Map<u32> = Map::read_entries(&mut bytes.as_slice(), 1000, read_u32).unwrap();
+    assert!(after.is_empty());
+}
+
+#[test]
+fn rejects_key_over_boundary() {
+    let bytes = [2, 1, 5, 0, 0, 0, 0];
+    let err = Map::read_entries(&mut &bytes[..], 1000, read_u32)
+        .err()
+        .unwrap();
+    assert_eq!(io::ErrorKind::InvalidData, err.kind());
+}
+
+#[test]
+fn rejects_duplicate_key() {
+    let bytes = [4, 2, 1, 5, 0, 0, 0, 1, 6, 0, 0, 0];
+    let err = Map::read_entries(&mut &bytes[..], 1000, read_u32)
+        .err()
+        .unwrap();
+    assert_eq!(io::ErrorKind::InvalidData, err.kind());
+    assert!(err.to_string().contains("The key 1 is met twice"));
+}
+
+#[test]
+fn rejects_truncated_entries() {
+    let bytes = [2, 1, 1, 0];
+    let err = Map::read_entries(&mut &bytes[..], 1000, read_u32)
+        .err()
+        .unwrap();
+    assert_eq!(io::ErrorKind::UnexpectedEof, err.kind());
+}
+
+#[test]
+fn rejects_huge_varint() {
+    let bytes = [0xFF; 11];
+    let err = Map::read_entries(&mut &bytes[..], 1000, read_u32)
+        .err()
+        .unwrap();
+    assert_eq!(io::ErrorKind::InvalidData, err.kind());
+}
+
+#[test]
+fn rejects_capacity_over_limit() {
+    let before: Map<u32> = Map::with_capacity_none(64);
+    let mut bytes = vec![];
+    before.write_entries(&mut bytes, write_u32).unwrap();
+    let err = Map::read_entries(&mut bytes.as_slice(), 63, read_u32)
+        .err()
+        .unwrap();
+    assert_eq!(io::ErrorKind::InvalidData, err.kind());
+    assert!(Map::read_entries(&mut bytes.as_slice(), 64, read_u32).is_ok());
+}
+
+#[test]
+fn rejects_hostile_capacity_without_allocating() {
+    let mut bytes = vec![];
+    write_varint(&mut bytes, usize::MAX / 2).unwrap();
+    write_varint(&mut bytes, 0).unwrap();
+    let err = Map::read_entries(&mut bytes.as_slice(), 1 << 20, read_u32)
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("is over the limit"));
+}
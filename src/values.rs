@@ -19,14 +19,15 @@
 // SOFTWARE.
 
 use crate::Map;
-use crate::{IntoValues, Values};
+use crate::{IntoValues, Keys, Values, ValuesMut};
+use std::iter::Sum;
 use std::marker::PhantomData;
+use std::ops::RangeBounds;
 
 impl<'a, V: Clone + 'a> Iterator for Values<'a, V> {
     type Item = &'a V;
 
     #[inline]
-    #[must_use]
     fn next(&mut self) -> Option<Self::Item> {
         while self.pos < self.max {
             let opt = unsafe { &*self.head.add(self.pos) };
@@ -39,11 +40,54 @@ impl<'a, V: Clone + 'a> Iterator for Values<'a, V> {
     }
 }
 
+impl<'a, V: Clone + 'a> DoubleEndedIterator for Values<'a, V> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while self.pos < self.max {
+            self.max -= 1;
+            let opt = unsafe { &*self.head.add(self.max) };
+            if opt.is_some() {
+                return opt.as_ref();
+            }
+        }
+        None
+    }
+}
+
+impl<'a, V: Clone + 'a> Iterator for ValuesMut<'a, V> {
+    type Item = &'a mut V;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pos < self.max {
+            let opt = unsafe { &mut *self.head.add(self.pos) };
+            self.pos += 1;
+            if opt.is_some() {
+                return opt.as_mut();
+            }
+        }
+        None
+    }
+}
+
+impl<'a, V: Clone + 'a> DoubleEndedIterator for ValuesMut<'a, V> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while self.pos < self.max {
+            self.max -= 1;
+            let opt = unsafe { &mut *self.head.add(self.max) };
+            if opt.is_some() {
+                return opt.as_mut();
+            }
+        }
+        None
+    }
+}
+
 impl<V: Copy> Iterator for IntoValues<V> {
     type Item = V;
 
     #[inline]
-    #[must_use]
     fn next(&mut self) -> Option<Self::Item> {
         while self.pos < self.max {
             let opt = unsafe { &*self.head.add(self.pos) };
@@ -56,6 +100,18 @@ impl<V: Copy> Iterator for IntoValues<V> {
     }
 }
 
+impl<V> Clone for Values<'_, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            max: self.max,
+            pos: self.pos,
+            head: self.head,
+            _marker: PhantomData,
+        }
+    }
+}
+
 impl<V: Clone> Map<V> {
     /// Make an iterator over all values.
     ///
@@ -64,7 +120,7 @@ impl<V: Clone> Map<V> {
     /// It may panic in debug mode, if the [`Map`] is not initialized.
     #[inline]
     #[must_use]
-    pub const fn values(&self) -> Values<V> {
+    pub const fn values(&self) -> Values<'_, V> {
         #[cfg(debug_assertions)]
         assert!(self.initialized, "Can't values() non-initialized Map");
         Values {
@@ -75,6 +131,79 @@ impl<V: Clone> Map<V> {
         }
     }
 
+    /// Make a mutable iterator over all values.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    #[must_use]
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn values_mut(&mut self) -> ValuesMut<'_, V> {
+        #[cfg(debug_assertions)]
+        assert!(self.initialized, "Can't values_mut() non-initialized Map");
+        ValuesMut {
+            max: self.max,
+            pos: 0,
+            head: self.head,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Make an iterator over the keys and a mutable iterator over the
+    /// values, which go in the same order and may be used independently.
+    ///
+    /// Neither of them allocates: the keys are read from the slots, while
+    /// the values are handed out one by one, the same way
+    /// [`Map::split_around`] splits the borrow of the map in two.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    pub const fn keys_values_mut(&mut self) -> (Keys<V>, ValuesMut<'_, V>) {
+        #[cfg(debug_assertions)]
+        assert!(
+            self.initialized,
+            "Can't keys_values_mut() non-initialized Map"
+        );
+        let keys = Keys {
+            max: self.max,
+            pos: 0,
+            head: self.head,
+        };
+        (
+            keys,
+            ValuesMut {
+                max: self.max,
+                pos: 0,
+                head: self.head,
+                _marker: PhantomData,
+            },
+        )
+    }
+
+    /// Make an iterator over the values with keys in the range.
+    ///
+    /// The range is clamped to the boundary of the map.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    #[must_use]
+    pub fn values_range<R: RangeBounds<usize>>(&self, range: R) -> Values<'_, V> {
+        #[cfg(debug_assertions)]
+        assert!(self.initialized, "Can't values_range() non-initialized Map");
+        let (pos, max) = self.clamp(&range);
+        Values {
+            max,
+            pos,
+            head: self.head,
+            _marker: PhantomData,
+        }
+    }
+
     /// Make an into-iterator over all items.
     ///
     /// # Panics
@@ -93,6 +222,43 @@ impl<V: Clone> Map<V> {
     }
 }
 
+impl<V: Copy + Sum> Map<V> {
+    /// Sum up all values.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    #[must_use]
+    pub fn sum_values(&self) -> V {
+        self.values().copied().sum()
+    }
+}
+
+impl<V: Clone + Ord> Map<V> {
+    /// Get the smallest value, or `None` if the map is empty.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    #[must_use]
+    pub fn min_value(&self) -> Option<&V> {
+        self.values().min()
+    }
+
+    /// Get the largest value, or `None` if the map is empty.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    #[must_use]
+    pub fn max_value(&self) -> Option<&V> {
+        self.values().max()
+    }
+}
+
 #[test]
 fn empty_values() {
     let m: Map<u32> = Map::with_capacity_none(16);
@@ -116,3 +282,112 @@ fn count_them_all() {
     m.insert(2, "three");
     assert_eq!(3, m.into_values().count());
 }
+
+#[test]
+fn takes_values_in_range() {
+    let mut m: Map<u32> = Map::with_capacity_none(16);
+    m.insert(1, 10);
+    m.insert(3, 30);
+    m.insert(8, 80);
+    assert_eq!(110, m.values_range(3..).sum::<u32>());
+    assert_eq!(40, m.values_range(0..8).sum::<u32>());
+}
+
+#[test]
+fn clones_values_iterator() {
+    let mut m: Map<&str> = Map::with_capacity_none(16);
+    m.insert(0, "one");
+    m.insert(1, "two");
+    let mut a = m.values();
+    let mut b = a.clone();
+    assert_eq!(Some(&"one"), a.next());
+    assert_eq!(Some(&"two"), a.next());
+    assert!(a.next().is_none());
+    assert_eq!(Some(&"one"), b.next());
+    assert_eq!(1, b.count());
+}
+
+#[test]
+fn iterates_values_backwards() {
+    let mut m: Map<u32> = Map::with_capacity_none(16);
+    m.insert(1, 10);
+    m.insert(4, 40);
+    m.insert(9, 90);
+    assert_eq!(
+        vec![90, 40, 10],
+        m.values().rev().copied().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn jumps_to_nth_value() {
+    let mut m: Map<u32> = Map::with_capacity_none(16);
+    m.insert(9, 90);
+    m.insert(1, 10);
+    m.insert(4, 40);
+    assert_eq!(Some(&40), m.values().nth(1));
+    assert_eq!(None, m.values().nth(3));
+}
+
+#[test]
+fn aggregates_values() {
+    let mut m: Map<i32> = Map::with_capacity_none(16);
+    for (k, v) in [(1, 7), (3, -2), (8, 12), (9, 5)] {
+        m.insert(k, v);
+    }
+    m.remove(9);
+    let sum = m.values().fold(0, |acc, v| acc + *v);
+    assert_eq!(sum, m.sum_values());
+    assert_eq!(Some(&-2), m.min_value());
+    assert_eq!(Some(&12), m.max_value());
+}
+
+#[test]
+fn aggregates_empty_values() {
+    let m: Map<i32> = Map::with_capacity_none(16);
+    assert_eq!(0, m.sum_values());
+    assert_eq!(None, m.min_value());
+    assert_eq!(None, m.max_value());
+}
+
+#[test]
+fn updates_values_in_place() {
+    let mut m: Map<u32> = Map::with_capacity_none(8);
+    m.insert(2, 20);
+    m.insert(5, 50);
+    for v in m.values_mut() {
+        *v += 1;
+    }
+    assert_eq!(vec![21, 51], m.values().copied().collect::<Vec<_>>());
+}
+
+#[test]
+fn splits_keys_and_values() {
+    let mut m: Map<usize> = Map::with_capacity_none(8);
+    m.insert(1, 0);
+    m.insert(4, 0);
+    m.insert(7, 0);
+    m.remove(4);
+    let (keys, values) = m.keys_values_mut();
+    for (k, v) in keys.zip(values) {
+        *v = k * 10;
+    }
+    let pairs: Vec<(usize, usize)> = m.iter_mut().map(|(k, v)| (k, *v)).collect();
+    assert_eq!(vec![(1, 10), (7, 70)], pairs);
+}
+
+#[test]
+fn updates_values_from_the_back() {
+    let mut m: Map<u32> = Map::with_capacity_none(8);
+    for k in 0..5 {
+        m.insert(k, 0);
+    }
+    m.remove(2);
+    let mut values = m.values_mut();
+    *values.next_back().unwrap() += 1;
+    *values.next().unwrap() += 2;
+    for (i, v) in values.rev().enumerate() {
+        *v += 10 + u32::try_from(i).unwrap();
+    }
+    assert_eq!(vec![2, 11, 10, 1], m.values().copied().collect::<Vec<_>>());
+}
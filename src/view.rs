@@ -0,0 +1,131 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::{Iter, Keys, Map, MapView, Values};
+use std::marker::PhantomData;
+
+impl<V: Clone> Map<V> {
+    /// Make a read-only view of the map, for hot reading paths.
+    ///
+    /// The view checks that the map is initialized only once, here,
+    /// while its methods skip the checks, even in "debug" mode.
+    ///
+    /// # Panics
+    ///
+    /// It may panic in debug mode, if the [`Map`] is not initialized.
+    #[inline]
+    #[must_use]
+    pub const fn view(&self) -> MapView<'_, V> {
+        #[cfg(debug_assertions)]
+        assert!(self.initialized, "Can't view() non-initialized Map");
+        MapView { map: self }
+    }
+}
+
+impl<'a, V: Clone> MapView<'a, V> {
+    /// Is it empty?
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the total number of items inside.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        (0..self.map.max)
+            .filter(|k| unsafe { &*self.map.head.add(*k) }.is_some())
+            .count()
+    }
+
+    /// Get a reference to a single value, or `None` if the key is absent
+    /// or outside of the boundary.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, k: usize) -> Option<&'a V> {
+        if k >= self.map.max {
+            return None;
+        }
+        unsafe { &*self.map.head.add(k) }.as_ref()
+    }
+
+    /// Make an iterator over all items.
+    #[inline]
+    #[must_use]
+    #[allow(clippy::iter_without_into_iter)]
+    pub const fn iter(&self) -> Iter<'a, V> {
+        Iter {
+            max: self.map.max,
+            pos: 0,
+            head: self.map.head,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Make an iterator over all keys.
+    #[inline]
+    #[must_use]
+    pub const fn keys(&self) -> Keys<V> {
+        Keys {
+            max: self.map.max,
+            pos: 0,
+            head: self.map.head,
+        }
+    }
+
+    /// Make an iterator over all values.
+    #[inline]
+    #[must_use]
+    pub const fn values(&self) -> Values<'a, V> {
+        Values {
+            max: self.map.max,
+            pos: 0,
+            head: self.map.head,
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[test]
+fn views_map_as_is() {
+    let mut m: Map<&str> = Map::with_capacity_none(16);
+    m.insert(1, "one");
+    m.insert(7, "seven");
+    m.insert(4, "four");
+    m.remove(4);
+    let v = m.view();
+    assert_eq!(m.len(), v.len());
+    assert!(!v.is_empty());
+    assert_eq!(m.get(7), v.get(7));
+    assert_eq!(None, v.get(4));
+    assert_eq!(None, v.get(100));
+    assert!(m.iter().eq(v.iter()));
+    assert!(m.keys().eq(v.keys()));
+    assert!(m.values().eq(v.values()));
+}
+
+#[test]
+fn views_empty_map() {
+    let m: Map<u32> = Map::with_capacity_none(0);
+    let v = m.view();
+    assert!(v.is_empty());
+    assert_eq!(0, v.iter().count());
+}
@@ -66,7 +66,7 @@ fn benchmark(total: usize) -> Vec<(&'static str, Duration, Duration)> {
             let mut sum = 0;
             for _ in 0..CAP {
                 v.push(&42);
-                for s in v.into_iter() {
+                for s in v.iter() {
                     sum += *s;
                 }
             }
@@ -166,7 +166,7 @@ fn benchmark(total: usize) -> Vec<(&'static str, Duration, Duration)> {
             for _ in 0..CAP {
                 v.push(&42);
             }
-            for i in CAP - 1..0 {
+            for i in (0..CAP).rev() {
                 v.remove(i);
             }
         },
@@ -174,7 +174,7 @@ fn benchmark(total: usize) -> Vec<(&'static str, Duration, Duration)> {
             for i in 0..CAP {
                 v.insert(i, &42);
             }
-            for i in CAP - 1..0 {
+            for i in (0..CAP).rev() {
                 v.remove(i);
             }
         }
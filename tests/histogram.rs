@@ -0,0 +1,43 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use emap::Map;
+
+#[test]
+fn counts_bucketed_keys() {
+    let samples: [usize; 10] = [3, 17, 22, 5, 38, 31, 9, 12, 35, 1];
+    let mut m: Map<u64> = Map::with_capacity_none(4);
+    for s in samples {
+        m.entry(s / 10).and_modify(|c| *c += 1).or_insert(1);
+    }
+    assert_eq!(
+        vec![(0, 4), (1, 2), (2, 1), (3, 3)],
+        m.iter().map(|(k, c)| (k, *c)).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+#[should_panic(expected = "The key 4 is over the boundary 4")]
+fn panics_when_bucket_does_not_fit() {
+    let mut m: Map<u64> = Map::with_capacity_none(4);
+    for s in [3, 45] {
+        m.entry(s / 10).and_modify(|c| *c += 1).or_insert(1);
+    }
+}
@@ -0,0 +1,69 @@
+// Copyright (c) 2023 Yegor Bugayenko
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use emap::{Map, Op};
+use proptest::prelude::*;
+use std::collections::HashMap;
+
+const CAP: usize = 16;
+
+fn op() -> impl Strategy<Value = Op<u32>> {
+    prop_oneof![
+        4 => (0..CAP, any::<u32>()).prop_map(|(k, v)| Op::Insert(k, v)),
+        3 => (0..CAP).prop_map(Op::Remove),
+        2 => any::<u32>().prop_map(Op::Push),
+        1 => Just(Op::Clear),
+    ]
+}
+
+/// Apply the operation to the oracle, the way the Map is expected to.
+fn model(oracle: &mut HashMap<usize, u32>, op: &Op<u32>) {
+    match op {
+        Op::Insert(k, v) => {
+            oracle.insert(*k, *v);
+        }
+        Op::Remove(k) => {
+            oracle.remove(k);
+        }
+        Op::Push(v) => {
+            let k = (0..CAP).find(|k| !oracle.contains_key(k)).unwrap();
+            oracle.insert(k, *v);
+        }
+        Op::Clear => oracle.clear(),
+    }
+}
+
+proptest! {
+    #[test]
+    fn behaves_like_hash_map(ops in prop::collection::vec(op(), 0..200)) {
+        let mut m: Map<u32> = Map::with_capacity_none(CAP);
+        let mut oracle: HashMap<usize, u32> = HashMap::new();
+        for op in ops {
+            if matches!(op, Op::Push(_)) && oracle.len() == CAP {
+                continue;
+            }
+            model(&mut oracle, &op);
+            m.apply_op(op);
+            prop_assert_eq!(Ok(()), m.validate());
+            prop_assert_eq!(oracle.len(), m.len());
+            prop_assert!(m == oracle);
+        }
+    }
+}